@@ -0,0 +1,40 @@
+use std::env;
+use std::time::Duration;
+
+use aoc::runner;
+use aoc::server::{self, Config};
+
+const USAGE: &str =
+    "Usage: aoc-server [--port <port>] [--max-body-bytes <bytes>] [--timeout-ms <ms>]";
+
+fn main() {
+    let mut config = Config {
+        port: 8022,
+        max_body_bytes: 1024 * 1024,
+        solve_timeout: Duration::from_secs(10),
+    };
+    let args: Vec<String> = env::args().skip(1).collect();
+    for option in args.chunks(2) {
+        match option {
+            [name, value] if name == "--port" => config.port = parse_option(name, value),
+            [name, value] if name == "--max-body-bytes" => {
+                config.max_body_bytes = parse_option(name, value)
+            }
+            [name, value] if name == "--timeout-ms" => {
+                config.solve_timeout = Duration::from_millis(parse_option(name, value))
+            }
+            _ => panic!("{USAGE}"),
+        }
+    }
+    server::serve(runner::repo_root(), config);
+}
+
+fn parse_option<T: std::str::FromStr>(name: &str, value: &str) -> T
+where
+    T::Err: std::fmt::Display,
+{
+    match value.parse::<T>() {
+        Err(why) => panic!("Invalid value for {name} ({value}): {why}"),
+        Ok(v) => v,
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+pub struct Problem {
+    pub severity: Severity,
+    // 1-based, None when the problem concerns the file as a whole
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        match self.line {
+            None => write!(f, "{severity}: {}", self.message),
+            Some(l) => write!(f, "{severity}: line {l}: {}", self.message),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Report {
+    pub problems: Vec<Problem>,
+}
+
+impl Report {
+    // Line indexes passed in are 0-based
+    pub fn error(&mut self, index: usize, message: String) {
+        self.add(Severity::Error, Some(index + 1), message);
+    }
+
+    pub fn warning(&mut self, index: usize, message: String) {
+        self.add(Severity::Warning, Some(index + 1), message);
+    }
+
+    pub fn file_error(&mut self, message: String) {
+        self.add(Severity::Error, None, message);
+    }
+
+    pub fn file_warning(&mut self, message: String) {
+        self.add(Severity::Warning, None, message);
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    fn add(&mut self, severity: Severity, line: Option<usize>, message: String) {
+        self.problems.push(Problem {
+            severity,
+            line,
+            message,
+        });
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.problems
+            .iter()
+            .filter(|p| p.severity == severity)
+            .count()
+    }
+}
+
+pub fn check(day: u32, text: &str) -> Option<Report> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut report = Report::default();
+    if lines.is_empty() {
+        report.file_error("File is empty".to_string());
+        return Some(report);
+    }
+    match day {
+        1 => day1::check(&lines, &mut report),
+        2 => day2::check(&lines, &mut report),
+        3 => day3::check(&lines, &mut report),
+        4 => day4::check(&lines, &mut report),
+        5 => day5::check(&lines, &mut report),
+        6 => day6::check(&lines, &mut report),
+        7 => day7::check(&lines, &mut report),
+        8 => day8::check(&lines, &mut report),
+        9 => day9::check(&lines, &mut report),
+        10 => day10::check(&lines, &mut report),
+        11 => day11::check(&lines, &mut report),
+        12 => day12::check(&lines, &mut report),
+        13 => day13::check(&lines, &mut report),
+        14 => day14::check(&lines, &mut report),
+        15 => day15::check(&lines, &mut report),
+        16 => day16::check(&lines, &mut report),
+        _ => return None,
+    }
+    report.problems.sort_by_key(|p| p.line.unwrap_or(0));
+    Some(report)
+}
+
+// Blocks of lines separated by blank lines, with the index of their first line.
+// Consecutive blank lines yield empty blocks, like `split(|l| l == "")` in the solvers.
+fn blocks<'a>(lines: &'a [&'a str]) -> Vec<(usize, &'a [&'a str])> {
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            blocks.push((start, &lines[start..i]));
+            start = i + 1;
+        }
+    }
+    blocks.push((start, &lines[start..]));
+    blocks
+}
+
+fn check_no_blank_lines(lines: &[&str], report: &mut Report) {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.is_empty())
+        .for_each(|(i, _)| report.error(i, "Unexpected blank line".to_string()));
+}
+
+fn check_grid<F>(lines: &[&str], report: &mut Report, is_valid_cell: F, description: &str)
+where
+    F: Fn(char) -> bool,
+{
+    let width = lines[0].chars().count();
+    for (i, line) in lines.iter().enumerate() {
+        let line_width = line.chars().count();
+        if line_width != width {
+            report.error(
+                i,
+                format!("Row has {line_width} cells, but the first row has {width}"),
+            );
+        }
+        if let Some((x, c)) = line.chars().enumerate().find(|(_, c)| !is_valid_cell(*c)) {
+            report.error(
+                i,
+                format!(
+                    "Invalid cell '{c}' at column {}, expected {description}",
+                    x + 1
+                ),
+            );
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(
+    text: &str,
+    name: &str,
+    index: usize,
+    report: &mut Report,
+) -> Option<T>
+where
+    T::Err: fmt::Display,
+{
+    match text.parse::<T>() {
+        Err(why) => {
+            report.error(index, format!("Invalid {name} '{text}': {why}"));
+            None
+        }
+        Ok(n) => Some(n),
+    }
+}
@@ -0,0 +1,33 @@
+use super::{blocks, parse_number, Report};
+
+// Numbers, one elf per block of lines separated by a blank line
+pub fn check(lines: &[&str], report: &mut Report) {
+    let blocks = blocks(lines);
+    let last_elf = blocks.len() - 1;
+    for (elf, (start, block)) in blocks.iter().enumerate() {
+        if block.is_empty() {
+            let index = start.saturating_sub(1);
+            let reason = if elf == 0 {
+                "a leading blank line"
+            } else if elf == last_elf {
+                "a trailing blank line"
+            } else {
+                "consecutive blank lines"
+            };
+            report.warning(
+                index,
+                format!("Elf {} has an empty inventory because of {reason}", elf + 1),
+            );
+        }
+        for (offset, line) in block.iter().enumerate() {
+            let index = start + offset;
+            if line.trim() != *line {
+                report.error(index, format!("Surrounding whitespace in '{line}'"));
+                continue;
+            }
+            if let Some(0) = parse_number::<u64>(line, "calorie amount", index, report) {
+                report.warning(index, "Item without calories".to_string());
+            }
+        }
+    }
+}
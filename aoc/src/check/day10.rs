@@ -0,0 +1,21 @@
+use super::{parse_number, Report};
+
+// "noop" or "addx <value>"
+pub fn check(lines: &[&str], report: &mut Report) {
+    for (i, line) in lines.iter().enumerate() {
+        if *line == "noop" {
+            continue;
+        }
+        match line.strip_prefix("addx ") {
+            None => report.error(
+                i,
+                format!("Expected 'noop' or 'addx <value>', but found '{line}'"),
+            ),
+            Some(value) => {
+                if let Some(0) = parse_number::<i32>(value, "value", i, report) {
+                    report.warning(i, "Adding zero".to_string());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use super::{blocks, parse_number, Report};
+
+const MONKEY_PREFIX: &str = "Monkey ";
+const ITEMS_PREFIX: &str = "  Starting items: ";
+const OPERATION_PREFIX: &str = "  Operation: new = old ";
+const DIVISIBLE_PREFIX: &str = "  Test: divisible by ";
+const TRUE_PREFIX: &str = "    If true: throw to monkey ";
+const FALSE_PREFIX: &str = "    If false: throw to monkey ";
+
+const BLOCK_LENGTH: usize = 6;
+
+// Blocks of six lines per monkey, separated by blank lines
+pub fn check(lines: &[&str], report: &mut Report) {
+    let blocks = blocks(lines);
+    let monkey_count = blocks.len();
+    for (monkey, (start, block)) in blocks.iter().enumerate() {
+        if block.len() < BLOCK_LENGTH {
+            let index = if block.is_empty() {
+                start.saturating_sub(1)
+            } else {
+                *start
+            };
+            report.error(
+                index,
+                format!(
+                    "Monkey block has {} lines, expected {BLOCK_LENGTH}",
+                    block.len()
+                ),
+            );
+            continue;
+        }
+        if block.len() > BLOCK_LENGTH {
+            report.warning(
+                start + BLOCK_LENGTH,
+                format!("Lines after the first {BLOCK_LENGTH} of a monkey are ignored"),
+            );
+        }
+        check_header(block[0], monkey, *start, report);
+        check_items(block[1], start + 1, report);
+        check_operation(block[2], start + 2, report);
+        if let Some(0) = check_number_line::<u32>(block[3], DIVISIBLE_PREFIX, start + 3, report) {
+            report.error(start + 3, "Divisor must not be zero".to_string());
+        }
+        for offset in [4, 5] {
+            let prefix = if offset == 4 {
+                TRUE_PREFIX
+            } else {
+                FALSE_PREFIX
+            };
+            let index = start + offset;
+            match check_number_line::<usize>(block[offset], prefix, index, report) {
+                Some(target) if target >= monkey_count => report.error(
+                    index,
+                    format!("Monkey {target} does not exist, there are {monkey_count} monkeys"),
+                ),
+                Some(target) if target == monkey => {
+                    report.warning(index, "Monkey throws to itself".to_string())
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn check_header(line: &str, monkey: usize, index: usize, report: &mut Report) {
+    match line
+        .strip_prefix(MONKEY_PREFIX)
+        .and_then(|l| l.strip_suffix(':'))
+    {
+        None => report.error(index, format!("Expected 'Monkey <n>:', but found '{line}'")),
+        Some(number) => {
+            if number != monkey.to_string() {
+                report.warning(
+                    index,
+                    format!("Monkey is numbered {number}, but is monkey {monkey} in order"),
+                );
+            }
+        }
+    }
+}
+
+fn check_items(line: &str, index: usize, report: &mut Report) {
+    match line.strip_prefix(ITEMS_PREFIX) {
+        None => report.error(
+            index,
+            format!("Expected '{ITEMS_PREFIX}', but found '{line}'"),
+        ),
+        Some(items) => items.split(", ").for_each(|item| {
+            parse_number::<u32>(item.trim(), "worry level", index, report);
+        }),
+    }
+}
+
+fn check_operation(line: &str, index: usize, report: &mut Report) {
+    let operation = match line.strip_prefix(OPERATION_PREFIX) {
+        None => {
+            report.error(
+                index,
+                format!("Expected '{OPERATION_PREFIX}', but found '{line}'"),
+            );
+            return;
+        }
+        Some(o) => o,
+    };
+    match operation.split_once(' ') {
+        None => report.error(
+            index,
+            format!("Expected '<operator> <operand>', but found '{operation}'"),
+        ),
+        Some((operator, operand)) => {
+            if operator != "*" && operator != "+" {
+                report.error(index, format!("Invalid operator '{operator}'"));
+            }
+            if operand != "old" {
+                parse_number::<u32>(operand.trim(), "operand", index, report);
+            }
+        }
+    }
+}
+
+fn check_number_line<T>(line: &str, prefix: &str, index: usize, report: &mut Report) -> Option<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    match line.strip_prefix(prefix) {
+        None => {
+            report.error(index, format!("Expected '{prefix}', but found '{line}'"));
+            None
+        }
+        Some(number) => parse_number::<T>(number.trim(), "number", index, report),
+    }
+}
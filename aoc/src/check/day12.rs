@@ -0,0 +1,25 @@
+use super::{check_grid, Report};
+
+// A rectangular height map of a-z with one start S and one end E
+pub fn check(lines: &[&str], report: &mut Report) {
+    check_grid(
+        lines,
+        report,
+        |c| c.is_ascii_lowercase() || c == 'S' || c == 'E',
+        "a-z, S or E",
+    );
+    for marker in ['S', 'E'] {
+        let positions: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .flat_map(|(i, l)| l.matches(marker).map(move |_| i))
+            .collect();
+        match positions.as_slice() {
+            [] => report.file_error(format!("No '{marker}' found")),
+            [_] => {}
+            [_, rest @ ..] => rest.iter().for_each(|i| {
+                report.warning(*i, format!("Another '{marker}', only the first is used"))
+            }),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::{blocks, Report};
+
+// Pairs of packet lines separated by blank lines
+pub fn check(lines: &[&str], report: &mut Report) {
+    for (start, block) in blocks(lines) {
+        if block.len() != 2 {
+            report.error(
+                if block.is_empty() {
+                    start.saturating_sub(1)
+                } else {
+                    start
+                },
+                format!(
+                    "Part 1: expected a pair of 2 packets, but found {}",
+                    block.len()
+                ),
+            );
+        }
+        for (offset, line) in block.iter().enumerate() {
+            let mut chars = line.chars().peekable();
+            if !line.starts_with('[') {
+                report.warning(start + offset, "Packet is not a list".to_string());
+            }
+            match check_entry(&mut chars) {
+                Err(why) => report.error(start + offset, why),
+                Ok(()) => {
+                    if let Some(c) = chars.next() {
+                        report.error(start + offset, format!("Unexpected '{c}' after packet"));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_entry(chars: &mut Peekable<Chars>) -> Result<(), String> {
+    match chars.peek() {
+        None => Err("Expected another entry".to_string()),
+        Some('[') => check_list(chars),
+        Some(_) => check_value(chars),
+    }
+}
+
+fn check_list(chars: &mut Peekable<Chars>) -> Result<(), String> {
+    chars.next();
+    if chars.peek() == Some(&']') {
+        chars.next();
+        return Ok(());
+    }
+    check_entry(chars)?;
+    loop {
+        match chars.next() {
+            None => return Err("Expected , or ]".to_string()),
+            Some(',') => check_entry(chars)?,
+            Some(']') => return Ok(()),
+            Some(c) => return Err(format!("Expected , or ], but found {c}")),
+        }
+    }
+}
+
+fn check_value(chars: &mut Peekable<Chars>) -> Result<(), String> {
+    let mut number = String::new();
+    while let Some(c) = chars.peek().filter(|c| **c == '-' || c.is_ascii_digit()) {
+        number.push(*c);
+        chars.next();
+    }
+    match number.parse::<i32>() {
+        Err(why) => Err(format!("Not a number '{number}': {why}")),
+        Ok(_) => Ok(()),
+    }
+}
@@ -0,0 +1,38 @@
+use super::{check_no_blank_lines, parse_number, Report};
+
+// "498,4 -> 498,6 -> 496,6": paths of horizontal and vertical rock lines
+pub fn check(lines: &[&str], report: &mut Report) {
+    check_no_blank_lines(lines, report);
+    for (i, line) in lines.iter().enumerate().filter(|(_, l)| !l.is_empty()) {
+        let points: Vec<Option<(u64, u64)>> = line
+            .split(" -> ")
+            .map(|point| match point.split_once(',') {
+                None => {
+                    report.error(i, format!("Expected '<x>,<y>', but found '{point}'"));
+                    None
+                }
+                Some((x, y)) => {
+                    let x = parse_number::<u64>(x, "x coordinate", i, report);
+                    let y = parse_number::<u64>(y, "y coordinate", i, report);
+                    x.zip(y)
+                }
+            })
+            .collect();
+        if points.len() == 1 {
+            report.warning(i, "Path of a single point".to_string());
+        }
+        for segment in points.windows(2) {
+            if let [Some(from), Some(to)] = segment {
+                if from.0 != to.0 && from.1 != to.1 {
+                    report.error(
+                        i,
+                        format!(
+                            "Diagonal segment {},{} -> {},{}",
+                            from.0, from.1, to.0, to.1
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::{check_no_blank_lines, parse_number, Report};
+
+const SENSOR_PREFIX: &str = "Sensor at ";
+const BEACON_PREFIX: &str = "closest beacon is at ";
+
+// "Sensor at x=2, y=18: closest beacon is at x=-2, y=15"
+pub fn check(lines: &[&str], report: &mut Report) {
+    check_no_blank_lines(lines, report);
+    for (i, line) in lines.iter().enumerate().filter(|(_, l)| !l.is_empty()) {
+        let (sensor, beacon) = match line.split_once(": ") {
+            None => {
+                report.error(
+                    i,
+                    format!("Expected '<sensor>: <beacon>', but found '{line}'"),
+                );
+                continue;
+            }
+            Some(parts) => parts,
+        };
+        let sensor = check_point(sensor, SENSOR_PREFIX, i, report);
+        let beacon = check_point(beacon, BEACON_PREFIX, i, report);
+        if sensor.is_some() && sensor == beacon {
+            report.warning(i, "Sensor is on its own beacon".to_string());
+        }
+    }
+}
+
+fn check_point(text: &str, prefix: &str, index: usize, report: &mut Report) -> Option<(i64, i64)> {
+    let point = match text.strip_prefix(prefix) {
+        None => {
+            report.error(index, format!("Expected '{prefix}', but found '{text}'"));
+            return None;
+        }
+        Some(p) => p,
+    };
+    let (x, y) = match point.split_once(", ") {
+        None => {
+            report.error(
+                index,
+                format!("Expected 'x=<x>, y=<y>', but found '{point}'"),
+            );
+            return None;
+        }
+        Some(parts) => parts,
+    };
+    let x = check_coordinate(x, "x=", index, report);
+    let y = check_coordinate(y, "y=", index, report);
+    x.zip(y)
+}
+
+fn check_coordinate(text: &str, prefix: &str, index: usize, report: &mut Report) -> Option<i64> {
+    match text.strip_prefix(prefix) {
+        None => {
+            report.error(index, format!("Expected '{prefix}', but found '{text}'"));
+            None
+        }
+        Some(number) => parse_number::<i64>(number, "coordinate", index, report),
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use super::{check_no_blank_lines, parse_number, Report};
+
+const START_VALVE: &str = "AA";
+// The exhaustive search gets very slow beyond this many valves worth opening
+const MAX_USEFUL_VALVES: usize = 15;
+
+// "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB"
+pub fn check(lines: &[&str], report: &mut Report) {
+    check_no_blank_lines(lines, report);
+    let mut valves: HashMap<&str, usize> = HashMap::new();
+    let mut tunnels: Vec<(usize, Vec<&str>)> = Vec::new();
+    let mut useful_valve_count = 0;
+    for (i, line) in lines.iter().enumerate().filter(|(_, l)| !l.is_empty()) {
+        let (valve, tunnel_part) = match line.split_once("; ") {
+            None => {
+                report.error(
+                    i,
+                    format!("Expected '<valve>; <tunnels>', but found '{line}'"),
+                );
+                continue;
+            }
+            Some(parts) => parts,
+        };
+        let (name, rate) = match valve
+            .strip_prefix("Valve ")
+            .and_then(|v| v.split_once(" has flow rate="))
+        {
+            None => {
+                report.error(
+                    i,
+                    format!("Expected 'Valve <name> has flow rate=<rate>', but found '{valve}'"),
+                );
+                continue;
+            }
+            Some(parts) => parts,
+        };
+        if let Some(previous) = valves.insert(name, i) {
+            report.warning(
+                i,
+                format!(
+                    "Valve {name} was already described on line {}",
+                    previous + 1
+                ),
+            );
+        }
+        if let Some(r) = parse_number::<u32>(rate, "flow rate", i, report) {
+            if r > 0 {
+                useful_valve_count += 1;
+            }
+        }
+        let targets = ["tunnels lead to valves ", "tunnel leads to valve "]
+            .iter()
+            .find_map(|prefix| tunnel_part.strip_prefix(prefix));
+        match targets {
+            None => report.error(
+                i,
+                format!("Expected 'tunnels lead to valves <names>', but found '{tunnel_part}'"),
+            ),
+            Some(t) => tunnels.push((i, t.split(", ").collect())),
+        }
+    }
+    for (i, targets) in tunnels {
+        targets
+            .iter()
+            .filter(|t| !valves.contains_key(*t))
+            .for_each(|t| report.error(i, format!("Tunnel leads to unknown valve {t}")));
+    }
+    if !valves.contains_key(START_VALVE) {
+        report.file_error(format!("No start valve {START_VALVE}"));
+    }
+    if useful_valve_count > MAX_USEFUL_VALVES {
+        report.file_warning(format!(
+            "{useful_valve_count} valves have a flow rate, solving may take very long"
+        ));
+    }
+}
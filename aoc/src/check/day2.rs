@@ -0,0 +1,43 @@
+use super::Report;
+
+// "A X": opponent A-C, space, X-Z. Only the default rock-paper-scissors letters are checked,
+// guides for a --rules variant with other letters are reported as invalid.
+pub fn check(lines: &[&str], report: &mut Report) {
+    for (i, line) in lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        if chars.len() < 3 {
+            report.error(i, format!("Expected '<A|B|C> <X|Y|Z>', but found '{line}'"));
+            continue;
+        }
+        if !('A'..='C').contains(&chars[0]) {
+            report.error(
+                i,
+                format!(
+                    "Invalid opponent move '{}', expected A-C of the default rules",
+                    chars[0]
+                ),
+            );
+        }
+        if chars[1] != ' ' {
+            report.error(i, format!("Expected space but found '{}'", chars[1]));
+        }
+        if !('X'..='Z').contains(&chars[2]) {
+            report.error(
+                i,
+                format!(
+                    "Invalid second column '{}', expected X-Z of the default rules",
+                    chars[2]
+                ),
+            );
+        }
+        if chars.len() > 3 {
+            report.warning(
+                i,
+                format!(
+                    "Trailing characters '{}' are ignored",
+                    chars[3..].iter().collect::<String>()
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use super::{check_no_blank_lines, Report};
+
+const GROUP_SIZE: usize = 3;
+
+// One rucksack of item letters per line; part 2 groups them by three.
+// Items are checked against the default a-z, A-Z alphabet.
+pub fn check(lines: &[&str], report: &mut Report) {
+    check_no_blank_lines(lines, report);
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(item) = line.chars().find(|c| !c.is_ascii_alphabetic()) {
+            report.error(
+                i,
+                format!("Invalid item '{item}', not in the default alphabet"),
+            );
+            continue;
+        }
+        // The solver splits compartments by item count, not bytes, and rejects odd counts
+        let items: Vec<char> = line.chars().collect();
+        if !items.len().is_multiple_of(2) {
+            report.error(
+                i,
+                format!(
+                    "Odd item count {}, compartments differ in size",
+                    items.len()
+                ),
+            );
+            continue;
+        }
+        let (compartment1, compartment2) = items.split_at(items.len() / 2);
+        let compartment1: String = compartment1.iter().collect();
+        let compartment2: String = compartment2.iter().collect();
+        let common = common_items(&[&compartment1, &compartment2]);
+        match common.chars().count() {
+            0 => report.error(
+                i,
+                "Part 1: no item appears in both compartments".to_string(),
+            ),
+            1 => {}
+            _ => report.warning(
+                i,
+                format!("Part 1: left out, items {common} are in both compartments"),
+            ),
+        }
+    }
+    // The solver diagnoses groups without a single badge and leaves them out of the sum
+    if !lines.len().is_multiple_of(GROUP_SIZE) {
+        report.warning(
+            lines.len() - 1,
+            format!(
+                "Part 2: last group only has {} elves and is left out",
+                lines.len() % GROUP_SIZE
+            ),
+        );
+    }
+    for (group_index, group) in lines.chunks(GROUP_SIZE).enumerate() {
+        if group.len() < GROUP_SIZE {
+            continue;
+        }
+        let index = group_index * GROUP_SIZE;
+        let badges = common_items(group);
+        match badges.chars().count() {
+            0 => report.warning(
+                index,
+                format!(
+                    "Part 2: group {} has no badge and is left out",
+                    group_index + 1
+                ),
+            ),
+            1 => {}
+            _ => report.warning(
+                index,
+                format!(
+                    "Part 2: group {} has several badges: {badges}",
+                    group_index + 1
+                ),
+            ),
+        }
+    }
+}
+
+fn common_items(rucksacks: &[&str]) -> String {
+    let mut common: Vec<char> = rucksacks[0]
+        .chars()
+        .filter(|item| rucksacks[1..].iter().all(|r| r.contains(*item)))
+        .collect();
+    common.sort();
+    common.dedup();
+    common.into_iter().collect()
+}
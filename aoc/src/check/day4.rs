@@ -0,0 +1,33 @@
+use super::{check_no_blank_lines, parse_number, Report};
+
+// "2-4,6-8"
+pub fn check(lines: &[&str], report: &mut Report) {
+    check_no_blank_lines(lines, report);
+    for (i, line) in lines.iter().enumerate().filter(|(_, l)| !l.is_empty()) {
+        // Any number of elves can share a line
+        for assignment in line.split(',') {
+            check_assignment(assignment, i, report);
+        }
+    }
+}
+
+fn check_assignment(assignment: &str, index: usize, report: &mut Report) {
+    let bounds: Vec<&str> = assignment.split('-').collect();
+    if bounds.len() != 2 {
+        report.error(
+            index,
+            format!("Expected '<lower>-<upper>', but found '{assignment}'"),
+        );
+        return;
+    }
+    let lower = parse_number::<i32>(bounds[0], "lower bound", index, report);
+    let upper = parse_number::<i32>(bounds[1], "upper bound", index, report);
+    if let (Some(l), Some(u)) = (lower, upper) {
+        if l > u {
+            report.error(
+                index,
+                format!("Assignment {assignment} has its bounds reversed"),
+            );
+        }
+    }
+}
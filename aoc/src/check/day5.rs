@@ -0,0 +1,222 @@
+use super::{parse_number, Report};
+
+const MOVE_PREFIX: &str = "move";
+
+// A drawing of crate stacks, a numbered footer, a blank line and "move 1 from 2 to 1" lines
+pub fn check(lines: &[&str], report: &mut Report) {
+    // The footer is the first line made of numbers only
+    let footer_index = match lines.iter().position(|l| {
+        !l.trim().is_empty() && l.split_whitespace().all(|w| w.parse::<usize>().is_ok())
+    }) {
+        None => {
+            report.file_error("No stack number line found".to_string());
+            return;
+        }
+        Some(i) => i,
+    };
+    let columns = check_footer(lines[footer_index], footer_index, report);
+    let mut heights = check_drawing(&lines[..footer_index], &columns, report);
+    match lines.get(footer_index + 1) {
+        Some(&"") => {}
+        _ => report.warning(
+            footer_index,
+            "Expected a blank line after the stack numbers".to_string(),
+        ),
+    }
+    let first_move = lines
+        .iter()
+        .position(|l| l.starts_with(MOVE_PREFIX))
+        .unwrap_or(lines.len());
+    for (i, line) in lines
+        .iter()
+        .enumerate()
+        .take(first_move)
+        .skip(footer_index + 1)
+    {
+        if !line.is_empty() {
+            report.warning(i, format!("Line '{line}' before the first move is ignored"));
+        }
+    }
+    if first_move == lines.len() {
+        report.file_warning("No move instructions found".to_string());
+    }
+    for (i, line) in lines.iter().enumerate().skip(first_move) {
+        check_move(line, i, &mut heights, report);
+    }
+}
+
+// The character range of every stack number, which must count up from 1
+fn check_footer(footer: &str, index: usize, report: &mut Report) -> Vec<(usize, usize)> {
+    let mut columns = Vec::new();
+    let mut start: Option<usize> = None;
+    let chars: Vec<char> = footer.chars().chain([' ']).collect();
+    for (i, c) in chars.iter().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                let number: String = chars[s..i].iter().collect();
+                if number != (columns.len() + 1).to_string() {
+                    report.error(
+                        index,
+                        format!(
+                            "Expected stack number {}, but found '{number}'",
+                            columns.len() + 1
+                        ),
+                    );
+                }
+                columns.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    columns
+}
+
+// The character range and label of every [crate] on the line
+fn check_crates(line: &str, index: usize, report: &mut Report) -> Vec<((usize, usize), String)> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut crates = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            ' ' => i += 1,
+            '[' => {
+                let end = match chars[i..].iter().position(|c| *c == ']') {
+                    None => {
+                        report.error(index, format!("Crate at column {} is not closed", i + 1));
+                        break;
+                    }
+                    Some(e) => i + e,
+                };
+                let label: String = chars[i + 1..end].iter().collect();
+                if label.is_empty() || label.contains(char::is_whitespace) {
+                    report.error(
+                        index,
+                        format!("Crate at column {} has an invalid label '{label}'", i + 1),
+                    );
+                } else {
+                    crates.push(((i, end + 1), label));
+                }
+                i = end + 1;
+            }
+            c => {
+                report.error(
+                    index,
+                    format!(
+                        "Unexpected '{c}' at column {}, expected '[X]' or spaces",
+                        i + 1
+                    ),
+                );
+                i += 1;
+            }
+        }
+    }
+    crates
+}
+
+// A crate belongs to the stack whose number it overlaps, read bottom up
+fn check_drawing(lines: &[&str], columns: &[(usize, usize)], report: &mut Report) -> Vec<usize> {
+    if lines.is_empty() {
+        report.file_warning("The drawing has no crates".to_string());
+    }
+    let mut heights = vec![0; columns.len()];
+    let mut closed = vec![false; columns.len()];
+    for (i, line) in lines.iter().enumerate().rev() {
+        let mut filled = vec![false; columns.len()];
+        for ((start, end), label) in check_crates(line, i, report) {
+            let below: Vec<usize> = (0..columns.len())
+                .filter(|c| columns[*c].0 < end && start < columns[*c].1)
+                .collect();
+            let stack = match below.as_slice() {
+                [stack] => *stack,
+                [] => {
+                    report.error(i, format!("Crate [{label}] is not above a stack number"));
+                    continue;
+                }
+                _ => {
+                    report.error(i, format!("Crate [{label}] is above several stack numbers"));
+                    continue;
+                }
+            };
+            if filled[stack] {
+                report.error(i, format!("Stack {} has two crates", stack + 1));
+            } else if closed[stack] {
+                report.error(
+                    i,
+                    format!(
+                        "Crate [{label}] floats above an empty slot of stack {}",
+                        stack + 1
+                    ),
+                );
+            } else {
+                heights[stack] += 1;
+            }
+            filled[stack] = true;
+        }
+        for (stack, f) in filled.iter().enumerate() {
+            if !f {
+                closed[stack] = true;
+            }
+        }
+    }
+    heights
+}
+
+// Tracks the stack heights so moves from an empty stack are reported
+fn check_move(line: &str, index: usize, heights: &mut [usize], report: &mut Report) {
+    let parts: Vec<&str> = line.split(' ').collect();
+    if parts.len() != 6 || parts[0] != "move" || parts[2] != "from" || parts[4] != "to" {
+        report.error(
+            index,
+            format!("Expected 'move <count> from <stack> to <stack>', but found '{line}'"),
+        );
+        return;
+    }
+    let count = parse_number::<usize>(parts[1], "count", index, report);
+    let from = parse_number::<usize>(parts[3], "from stack", index, report);
+    let to = parse_number::<usize>(parts[5], "to stack", index, report);
+    let (count, from, to) = match (count, from, to) {
+        (Some(c), Some(f), Some(t)) => (c, f, t),
+        _ => return,
+    };
+    let mut valid = true;
+    for stack in [from, to] {
+        if stack == 0 || stack > heights.len() {
+            report.error(
+                index,
+                format!(
+                    "Stack {stack} does not exist, there are {} stacks",
+                    heights.len()
+                ),
+            );
+            valid = false;
+        }
+    }
+    if !valid {
+        return;
+    }
+    if count == 0 {
+        report.warning(index, "Moving zero crates".to_string());
+    }
+    if from == to {
+        report.warning(
+            index,
+            format!("Moving crates from stack {from} onto itself"),
+        );
+    }
+    if count > heights[from - 1] {
+        report.error(
+            index,
+            format!(
+                "Not enough crates: moving {count} from stack {from}, which only has {}",
+                heights[from - 1]
+            ),
+        );
+        heights[to - 1] += heights[from - 1];
+        heights[from - 1] = 0;
+    } else {
+        heights[from - 1] -= count;
+        heights[to - 1] += count;
+    }
+}
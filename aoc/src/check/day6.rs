@@ -0,0 +1,31 @@
+use super::Report;
+
+const PACKET_MARKER_SIZE: usize = 4;
+const MESSAGE_MARKER_SIZE: usize = 14;
+
+// One datastream of lowercase letters per line
+pub fn check(lines: &[&str], report: &mut Report) {
+    if lines.len() > 1 {
+        report.file_warning(format!(
+            "Found {} datastreams, each line is solved separately",
+            lines.len()
+        ));
+    }
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(c) = line.chars().find(|c| !c.is_ascii_lowercase()) {
+            report.warning(i, format!("Unexpected character '{c}' in datastream"));
+        }
+        let chars: Vec<char> = line.chars().collect();
+        if !has_marker(&chars, PACKET_MARKER_SIZE) {
+            report.error(i, "No start-of-packet marker".to_string());
+        } else if !has_marker(&chars, MESSAGE_MARKER_SIZE) {
+            report.warning(i, "No start-of-message marker".to_string());
+        }
+    }
+}
+
+fn has_marker(chars: &[char], size: usize) -> bool {
+    chars
+        .windows(size)
+        .any(|window| (0..size).all(|i| !window[i + 1..].contains(&window[i])))
+}
@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+
+use super::{check_no_blank_lines, parse_number, Report};
+
+const HEADER: &str = "$ cd /";
+const CD_PREFIX: &str = "$ cd ";
+const DIR_LISTING: &str = "$ ls";
+const DIR_PREFIX: &str = "dir ";
+const CD_EXIT: &str = "$ cd ..";
+
+// A terminal session that starts with "$ cd /" and lists every directory it enters
+pub fn check(lines: &[&str], report: &mut Report) {
+    check_no_blank_lines(lines, report);
+    if lines[0] != HEADER {
+        report.error(0, format!("Expected '{HEADER}' but found '{}'", lines[0]));
+    }
+    let mut path: Vec<&str> = Vec::new();
+    let mut listed_dirs: Vec<HashSet<&str>> = vec![HashSet::new()];
+    for (i, line) in lines.iter().enumerate().skip(1) {
+        if *line == CD_EXIT {
+            if path.pop().is_none() {
+                report.warning(i, "Leaving the root directory ends the session".to_string());
+            } else {
+                listed_dirs.pop();
+            }
+        } else if let Some(name) = line.strip_prefix(CD_PREFIX) {
+            if name == "/" {
+                report.warning(
+                    i,
+                    "Returning to the root directory is not supported".to_string(),
+                );
+            }
+            if !listed_dirs
+                .last()
+                .map(|d| d.contains(name))
+                .unwrap_or(false)
+            {
+                report.warning(
+                    i,
+                    format!("Entering directory '{name}' that was not listed"),
+                );
+            }
+            path.push(name);
+            listed_dirs.push(HashSet::new());
+            if lines.get(i + 1) != Some(&DIR_LISTING) {
+                report.error(
+                    i,
+                    format!("Expected '{DIR_LISTING}' after entering '{name}'"),
+                );
+            }
+        } else if *line == DIR_LISTING {
+            if i != 1 && !lines[i - 1].starts_with(CD_PREFIX) {
+                report.warning(i, "Listing the same directory twice".to_string());
+            }
+        } else if let Some(name) = line.strip_prefix(DIR_PREFIX) {
+            if let Some(dirs) = listed_dirs.last_mut() {
+                dirs.insert(name);
+            }
+        } else if line.starts_with('$') {
+            report.error(i, format!("Unknown command '{line}'"));
+        } else {
+            check_file(line, i, report);
+        }
+    }
+}
+
+fn check_file(line: &str, index: usize, report: &mut Report) {
+    match line.split_once(' ') {
+        None => report.error(
+            index,
+            format!("Expected '<size> <name>', but found '{line}'"),
+        ),
+        Some((size, name)) => {
+            parse_number::<u32>(size, "file size", index, report);
+            if name.is_empty() {
+                report.warning(index, "File without a name".to_string());
+            }
+        }
+    }
+}
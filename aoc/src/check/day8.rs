@@ -0,0 +1,6 @@
+use super::{check_grid, Report};
+
+// A rectangular grid of tree heights 0-9
+pub fn check(lines: &[&str], report: &mut Report) {
+    check_grid(lines, report, |c| c.is_ascii_digit(), "a digit");
+}
@@ -0,0 +1,23 @@
+use super::{parse_number, Report};
+
+// "R 4": a direction U/R/D/L and a step count
+pub fn check(lines: &[&str], report: &mut Report) {
+    for (i, line) in lines.iter().enumerate() {
+        let (direction, count) = match line.split_once(' ') {
+            None => {
+                report.error(
+                    i,
+                    format!("Expected '<U|R|D|L> <count>', but found '{line}'"),
+                );
+                continue;
+            }
+            Some(parts) => parts,
+        };
+        if !["U", "R", "D", "L"].contains(&direction) {
+            report.error(i, format!("Unknown direction '{direction}'"));
+        }
+        if let Some(0) = parse_number::<u8>(count, "count", i, report) {
+            report.warning(i, "Moving zero steps".to_string());
+        }
+    }
+}
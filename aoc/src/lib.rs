@@ -0,0 +1,5 @@
+pub mod check;
+pub mod runner;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod watch;
@@ -0,0 +1,52 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use aoc::{check, runner, watch};
+
+const USAGE: &str = "Usage: aoc watch <day> | aoc check <day> <file>";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let root = runner::repo_root();
+    match args
+        .iter()
+        .map(|a| a.as_str())
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        ["watch", day] => watch::watch(&root, parse_day(day)),
+        ["check", day, file] => check_file(parse_day(day), file),
+        _ => panic!("{USAGE}"),
+    }
+}
+
+fn parse_day(day: &str) -> u32 {
+    match day.parse::<u32>() {
+        Err(why) => panic!("Invalid day {day}: {why}"),
+        Ok(d) => d,
+    }
+}
+
+fn check_file(day: u32, filename: &str) {
+    let text = match fs::read_to_string(filename) {
+        Err(why) => panic!("couldn't read {filename}: {why}"),
+        Ok(t) => t,
+    };
+    let report = match check::check(day, &text) {
+        None => panic!("No input grammar for day {day}"),
+        Some(r) => r,
+    };
+    report
+        .problems
+        .iter()
+        .for_each(|p| println!("{filename}: {p}"));
+    println!(
+        "{} errors, {} warnings",
+        report.error_count(),
+        report.warning_count()
+    );
+    if report.error_count() > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,178 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+static SCRATCH_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Part {
+    pub day: u32,
+    pub part: u32,
+}
+
+impl Part {
+    pub fn new(day: u32, part: u32) -> Self {
+        Part { day, part }
+    }
+
+    pub fn name(&self) -> String {
+        format!("day{}part{}", self.day, self.part)
+    }
+
+    pub fn dir(&self, root: &Path) -> PathBuf {
+        root.join(self.name())
+    }
+
+    pub fn binary(&self, root: &Path) -> PathBuf {
+        self.dir(root)
+            .join("target")
+            .join("release")
+            .join(self.name())
+    }
+}
+
+pub struct Run {
+    pub line: String,
+    pub answer: String,
+    pub duration: Duration,
+}
+
+// The day crates live next to this one
+pub fn repo_root() -> PathBuf {
+    match Path::new(env!("CARGO_MANIFEST_DIR")).parent() {
+        None => panic!("aoc crate has no parent directory"),
+        Some(p) => p.to_path_buf(),
+    }
+}
+
+pub fn parts_of_day(root: &Path, day: u32) -> Vec<Part> {
+    (1..=2)
+        .map(|part| Part::new(day, part))
+        .filter(|p| p.dir(root).join("Cargo.toml").is_file())
+        .collect()
+}
+
+pub fn build(root: &Path, part: &Part) -> Result<PathBuf, String> {
+    let manifest = part.dir(root).join("Cargo.toml");
+    let output = Command::new("cargo")
+        .args(["build", "--release", "--quiet", "--manifest-path"])
+        .arg(&manifest)
+        .output();
+    match output {
+        Err(why) => Err(format!("couldn't start cargo: {why}")),
+        Ok(o) if !o.status.success() => Err(String::from_utf8_lossy(&o.stderr).to_string()),
+        Ok(_) => Ok(part.binary(root)),
+    }
+}
+
+// The solvers read "input.txt" from their working directory
+pub fn run_in_dir(
+    binary: &Path,
+    work_dir: &Path,
+    envs: &[(&str, String)],
+    timeout: Option<Duration>,
+) -> Result<Run, String> {
+    let start = Instant::now();
+    let mut child = match Command::new(binary)
+        .current_dir(work_dir)
+        .envs(envs.iter().map(|(k, v)| (*k, v)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Err(why) => return Err(format!("couldn't start {}: {why}", binary.display())),
+        Ok(c) => c,
+    };
+    let stdout_reader = spawn_reader(child.stdout.take());
+    let stderr_reader = spawn_reader(child.stderr.take());
+    let status = loop {
+        match child.try_wait() {
+            Err(why) => return Err(format!("couldn't wait for solver: {why}")),
+            Ok(Some(status)) => break status,
+            Ok(None) => {}
+        }
+        if let Some(t) = timeout {
+            if start.elapsed() > t {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {} ms", t.as_millis()));
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let duration = start.elapsed();
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if !status.success() {
+        return Err(
+            panic_message(&stderr).unwrap_or_else(|| format!("solver exited with {status}"))
+        );
+    }
+    let line = match last_line(&stdout) {
+        None => return Err("solver printed nothing".to_string()),
+        Some(l) => l,
+    };
+    let answer = line.split_whitespace().last().unwrap_or("").to_string();
+    Ok(Run {
+        line,
+        answer,
+        duration,
+    })
+}
+
+pub fn run_with_input(
+    binary: &Path,
+    input: &[u8],
+    envs: &[(&str, String)],
+    timeout: Option<Duration>,
+) -> Result<Run, String> {
+    let scratch = std::env::temp_dir().join(format!(
+        "aoc-{}-{}",
+        std::process::id(),
+        SCRATCH_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    if let Err(why) = fs::create_dir_all(&scratch) {
+        return Err(format!("couldn't create {}: {why}", scratch.display()));
+    }
+    let result = match fs::write(scratch.join("input.txt"), input) {
+        Err(why) => Err(format!("couldn't write input: {why}")),
+        Ok(()) => run_in_dir(binary, &scratch, envs, timeout),
+    };
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+fn spawn_reader<R: Read + Send + 'static>(source: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut s) = source {
+            let _ = s.read_to_string(&mut text);
+        }
+        text
+    })
+}
+
+fn last_line(text: &str) -> Option<String> {
+    text.lines()
+        .rev()
+        .map(|l| l.trim())
+        .find(|l| !l.is_empty())
+        .map(|l| l.to_string())
+}
+
+// thread 'main' panicked at src/main.rs:35:27:
+// not a number (x): invalid digit found in string
+fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines();
+    match lines.position(|l| l.contains("panicked at")) {
+        None => last_line(stderr),
+        Some(_) => lines.next().map(|l| l.trim().to_string()),
+    }
+}
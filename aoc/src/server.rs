@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Take, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::runner::{self, Part};
+
+const MAX_HEADER_LINES: usize = 100;
+// For the request line and all headers together
+const MAX_HEADER_BYTES: u64 = 16 * 1024;
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(10);
+// Solvers that check a budget stop themselves at the timeout, the others are killed a bit later
+const KILL_GRACE: Duration = Duration::from_millis(500);
+
+pub struct Config {
+    pub port: u16,
+    pub max_body_bytes: usize,
+    pub solve_timeout: Duration,
+}
+
+struct Server {
+    root: PathBuf,
+    config: Config,
+    binaries: Mutex<HashMap<Part, Arc<Mutex<Option<PathBuf>>>>>,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+}
+
+pub fn serve(root: PathBuf, config: Config) {
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, config.port)) {
+        Err(why) => panic!("couldn't bind to port {}: {why}", config.port),
+        Ok(l) => l,
+    };
+    println!(
+        "Listening on http://{}:{}",
+        Ipv4Addr::LOCALHOST,
+        config.port
+    );
+    println!("POST the input to /day/<day>/part/<part>");
+    let server = Arc::new(Server {
+        root,
+        config,
+        binaries: Mutex::new(HashMap::new()),
+    });
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Err(why) => {
+                println!("couldn't accept connection: {why}");
+                continue;
+            }
+            Ok(s) => s,
+        };
+        let server = Arc::clone(&server);
+        thread::spawn(move || server.handle(stream));
+    }
+}
+
+impl Server {
+    fn handle(&self, mut stream: TcpStream) {
+        let _ = stream.set_read_timeout(Some(SOCKET_TIMEOUT));
+        let _ = stream.set_write_timeout(Some(SOCKET_TIMEOUT));
+        let response = match stream.try_clone() {
+            Err(why) => Response::error(500, &why.to_string()),
+            Ok(s) => self.respond(&mut BufReader::new(s)),
+        };
+        let reason = match response.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            431 => "Request Header Fields Too Large",
+            422 => "Unprocessable Entity",
+            504 => "Gateway Timeout",
+            _ => "Internal Server Error",
+        };
+        let _ = write!(
+            stream,
+            "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status,
+            response.body.len(),
+            response.body
+        );
+    }
+
+    fn respond(&self, reader: &mut BufReader<TcpStream>) -> Response {
+        let mut head = reader.by_ref().take(MAX_HEADER_BYTES);
+        let mut request_line = String::new();
+        if let Err(why) = read_head_line(&mut head, &mut request_line) {
+            return why;
+        }
+        let mut content_length: Option<usize> = None;
+        let mut headers_ended = false;
+        // One more line than the limit for the blank line that ends the headers
+        for _ in 0..=MAX_HEADER_LINES {
+            let mut header = String::new();
+            if let Err(why) = read_head_line(&mut head, &mut header) {
+                return why;
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                headers_ended = true;
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = match value.trim().parse::<usize>() {
+                        Err(_) => return Response::error(400, "Invalid Content-Length"),
+                        Ok(l) => Some(l),
+                    };
+                }
+            }
+        }
+        if !headers_ended {
+            return Response::error(
+                431,
+                &format!("Requests are limited to {MAX_HEADER_LINES} headers"),
+            );
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or("");
+        let path = parts.next().unwrap_or("");
+        let part = match parse_path(path) {
+            None => return Response::error(404, &format!("No solver at '{path}'")),
+            Some(p) => p,
+        };
+        if !part.dir(&self.root).join("Cargo.toml").is_file() {
+            return Response::error(404, &format!("No solver for {}", part.name()));
+        }
+        if method != "POST" {
+            return Response::error(405, "Use POST with the input as body");
+        }
+        let length = match content_length {
+            None => return Response::error(400, "Content-Length is required"),
+            Some(l) => l,
+        };
+        if length > self.config.max_body_bytes {
+            return Response::error(
+                413,
+                &format!("Input is limited to {} bytes", self.config.max_body_bytes),
+            );
+        }
+        let mut input = vec![0; length];
+        if let Err(why) = reader.read_exact(&mut input) {
+            return Response::error(400, &why.to_string());
+        }
+        self.solve(&part, &input)
+    }
+
+    fn solve(&self, part: &Part, input: &[u8]) -> Response {
+        let build_start = Instant::now();
+        let binary = match self.binary(part) {
+            Err(why) => return Response::error(500, &why),
+            Ok(b) => b,
+        };
+        let build_duration = build_start.elapsed();
+        let timeout = self.config.solve_timeout;
+        let envs = [(budget::TIME_LIMIT_VARIABLE, timeout.as_millis().to_string())];
+        match runner::run_with_input(&binary, input, &envs, Some(timeout + KILL_GRACE)) {
+            Err(why) if why.starts_with("timed out") || why.starts_with("Budget exceeded") => {
+                Response::error(504, &why)
+            }
+            Err(why) => Response::error(422, &why),
+            Ok(run) => Response {
+                status: 200,
+                body: format!(
+                    "{{\"day\":{},\"part\":{},\"answer\":{},\"line\":{},\"timings\":{{\"build_ms\":{},\"solve_ms\":{}}}}}",
+                    part.day,
+                    part.part,
+                    json_string(&run.answer),
+                    json_string(&run.line),
+                    build_duration.as_millis(),
+                    run.duration.as_millis()
+                ),
+            },
+        }
+    }
+
+    // Builds each solver once; concurrent requests for the same part wait for that build
+    fn binary(&self, part: &Part) -> Result<PathBuf, String> {
+        let slot = match self.binaries.lock() {
+            Err(_) => return Err("Solver cache is poisoned".to_string()),
+            Ok(mut binaries) => Arc::clone(binaries.entry(*part).or_default()),
+        };
+        let mut binary = match slot.lock() {
+            Err(_) => return Err("Solver cache is poisoned".to_string()),
+            Ok(b) => b,
+        };
+        if let Some(b) = binary.as_ref() {
+            return Ok(b.clone());
+        }
+        let built = runner::build(&self.root, part)?;
+        *binary = Some(built.clone());
+        Ok(built)
+    }
+}
+
+// A line cut short by the header limit is rejected rather than read on
+fn read_head_line<R: BufRead>(head: &mut Take<R>, line: &mut String) -> Result<(), Response> {
+    match head.read_line(line) {
+        Err(why) => Err(Response::error(400, &why.to_string())),
+        Ok(0) => Err(Response::error(400, "Unexpected end of headers")),
+        Ok(_) if !line.ends_with('\n') && head.limit() == 0 => Err(Response::error(
+            431,
+            &format!("Headers are limited to {MAX_HEADER_BYTES} bytes"),
+        )),
+        Ok(_) => Ok(()),
+    }
+}
+
+// /day/<day>/part/<part>
+fn parse_path(path: &str) -> Option<Part> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["day", day, "part", part] => Some(Part::new(day.parse().ok()?, part.parse().ok()?)),
+        _ => None,
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
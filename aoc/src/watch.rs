@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::runner::{self, Part};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const EXAMPLE_PREFIX: &str = "example";
+const EXPECTED_EXTENSION: &str = "answer";
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+type Answers = BTreeMap<String, String>;
+
+pub fn watch(root: &Path, day: u32) {
+    let parts = runner::parts_of_day(root, day);
+    if parts.is_empty() {
+        panic!("No crates found for day {day} in {}", root.display());
+    }
+    let mut snapshot = Snapshot::new();
+    let mut previous = Answers::new();
+    loop {
+        let new_snapshot = take_snapshot(root, &parts);
+        if new_snapshot != snapshot {
+            snapshot = new_snapshot;
+            let answers = run_day(root, &parts);
+            print_diff(&previous, &answers);
+            previous = answers;
+            println!("Watching day {day} for changes...");
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn take_snapshot(root: &Path, parts: &[Part]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for part in parts {
+        let dir = part.dir(root);
+        add_modified(&dir.join("input.txt"), &mut snapshot);
+        examples(&dir).iter().for_each(|(example, expected)| {
+            add_modified(example, &mut snapshot);
+            add_modified(expected, &mut snapshot);
+        });
+        add_crate(&dir, &mut snapshot);
+    }
+    snapshot
+}
+
+// The crate's manifest and sources, and those of the crates it depends on by path
+fn add_crate(dir: &Path, snapshot: &mut Snapshot) {
+    let manifest = dir.join("Cargo.toml");
+    if snapshot.contains_key(&manifest) {
+        return;
+    }
+    add_modified(&manifest, snapshot);
+    add_tree(&dir.join("src"), snapshot);
+    for dependency in path_dependencies(&manifest) {
+        add_crate(&dir.join(dependency), snapshot);
+    }
+}
+
+// Only inline tables are read: name = { path = "../name" }
+fn path_dependencies(manifest: &Path) -> Vec<String> {
+    let text = match fs::read_to_string(manifest) {
+        Err(_) => return Vec::new(),
+        Ok(t) => t,
+    };
+    text.lines()
+        .filter_map(|line| line.split_once('{').map(|(_, table)| table))
+        .flat_map(|table| table.split(','))
+        .filter_map(|entry| entry.split_once('='))
+        .filter(|(key, _)| key.trim() == "path")
+        .map(|(_, value)| {
+            value
+                .trim()
+                .trim_end_matches('}')
+                .trim()
+                .trim_matches('"')
+                .to_string()
+        })
+        .collect()
+}
+
+fn add_tree(dir: &Path, snapshot: &mut Snapshot) {
+    let entries = match fs::read_dir(dir) {
+        Err(_) => return,
+        Ok(e) => e,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            add_tree(&path, snapshot);
+        } else {
+            add_modified(&path, snapshot);
+        }
+    }
+}
+
+fn add_modified(path: &Path, snapshot: &mut Snapshot) {
+    if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
+
+// example.txt, example2.txt, ... with the expected answer in example.answer, example2.answer, ...
+fn examples(dir: &Path) -> Vec<(PathBuf, PathBuf)> {
+    let entries = match fs::read_dir(dir) {
+        Err(_) => return Vec::new(),
+        Ok(e) => e,
+    };
+    let mut examples: Vec<(PathBuf, PathBuf)> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            name.starts_with(EXAMPLE_PREFIX) && name.ends_with(".txt")
+        })
+        .map(|p| {
+            let expected = p.with_extension(EXPECTED_EXTENSION);
+            (p, expected)
+        })
+        .collect();
+    examples.sort();
+    examples
+}
+
+fn run_day(root: &Path, parts: &[Part]) -> Answers {
+    let mut answers = Answers::new();
+    for part in parts {
+        let name = part.name();
+        println!("Building {name}");
+        let binary = match runner::build(root, part) {
+            Err(why) => {
+                println!("{why}");
+                answers.insert(format!("{name} input.txt"), "build failed".to_string());
+                continue;
+            }
+            Ok(b) => b,
+        };
+        let dir = part.dir(root);
+        let answer = match runner::run_in_dir(&binary, &dir, &[], None) {
+            Err(why) => format!("error: {why}"),
+            Ok(run) => {
+                println!(
+                    "  {name} input.txt: {} ({} ms)",
+                    run.line,
+                    run.duration.as_millis()
+                );
+                run.answer
+            }
+        };
+        answers.insert(format!("{name} input.txt"), answer);
+        for (example, expected) in examples(&dir) {
+            let example_name = example.file_name().unwrap_or_default().to_string_lossy();
+            let key = format!("{name} {example_name}");
+            let input = match fs::read(&example) {
+                Err(why) => {
+                    answers.insert(key, format!("error: {why}"));
+                    continue;
+                }
+                Ok(i) => i,
+            };
+            let answer = match runner::run_with_input(&binary, &input, &[], None) {
+                Err(why) => format!("error: {why}"),
+                Ok(run) => run.answer,
+            };
+            let verdict = match fs::read_to_string(&expected) {
+                Err(_) => String::new(),
+                Ok(e) if e.trim() == answer => " [pass]".to_string(),
+                Ok(e) => format!(" [FAIL, expected {}]", e.trim()),
+            };
+            answers.insert(key, format!("{answer}{verdict}"));
+        }
+    }
+    answers
+}
+
+fn print_diff(previous: &Answers, answers: &Answers) {
+    let width = answers.keys().map(|k| k.len()).max().unwrap_or(0);
+    for (key, answer) in answers {
+        match previous.get(key) {
+            None => println!("{key:width$}  {answer}"),
+            Some(p) if p == answer => println!("{key:width$}  {answer} (unchanged)"),
+            Some(p) => println!("{key:width$}  {p} -> {answer}"),
+        }
+    }
+    previous
+        .keys()
+        .filter(|k| !answers.contains_key(*k))
+        .for_each(|k| println!("{k:width$}  (removed)"));
+}
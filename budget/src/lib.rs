@@ -0,0 +1,119 @@
+//! A cancellation token and the time and iteration limits of the slow solvers
+
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const TIME_LIMIT_VARIABLE: &str = "AOC_TIME_LIMIT_MS";
+const ITERATION_LIMIT_VARIABLE: &str = "AOC_ITERATION_LIMIT";
+
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    // Keeps the clock out of the solvers' inner loops
+    pub fn cancel_after(&self, duration: Duration) {
+        let token = self.clone();
+        thread::spawn(move || {
+            thread::sleep(duration);
+            token.cancel();
+        });
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+pub enum BudgetExceeded {
+    Cancelled,
+    TimeLimit(Duration),
+    IterationLimit(u64),
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BudgetExceeded::Cancelled => write!(f, "cancelled"),
+            BudgetExceeded::TimeLimit(limit) => {
+                write!(f, "time limit of {} ms exceeded", limit.as_millis())
+            }
+            BudgetExceeded::IterationLimit(limit) => {
+                write!(f, "iteration limit of {limit} exceeded")
+            }
+        }
+    }
+}
+
+pub struct Budget {
+    token: CancellationToken,
+    start: Instant,
+    time_limit: Option<Duration>,
+    iteration_limit: Option<u64>,
+    iterations: u64,
+}
+
+impl Budget {
+    pub fn new(
+        token: CancellationToken,
+        time_limit: Option<Duration>,
+        iteration_limit: Option<u64>,
+    ) -> Self {
+        // Taken before the timer starts, so a time limit always shows as elapsed once it fires
+        let start = Instant::now();
+        if let Some(limit) = time_limit {
+            token.cancel_after(limit);
+        }
+        Budget {
+            token,
+            start,
+            time_limit,
+            iteration_limit,
+            iterations: 0,
+        }
+    }
+
+    // Both limits are optional, without them only cancellation stops the solver
+    pub fn from_env(token: CancellationToken) -> Self {
+        let time_limit = read_limit(TIME_LIMIT_VARIABLE).map(Duration::from_millis);
+        let iteration_limit = read_limit(ITERATION_LIMIT_VARIABLE);
+        Budget::new(token, time_limit, iteration_limit)
+    }
+
+    pub fn check(&mut self) -> Result<(), BudgetExceeded> {
+        self.iterations += 1;
+        if let Some(limit) = self.iteration_limit {
+            if self.iterations > limit {
+                return Err(BudgetExceeded::IterationLimit(limit));
+            }
+        }
+        if !self.token.is_cancelled() {
+            return Ok(());
+        }
+        match self.time_limit {
+            Some(limit) if self.start.elapsed() >= limit => Err(BudgetExceeded::TimeLimit(limit)),
+            _ => Err(BudgetExceeded::Cancelled),
+        }
+    }
+}
+
+fn read_limit(variable: &str) -> Option<u64> {
+    let value = env::var(variable).ok()?;
+    match value.parse::<u64>() {
+        Err(why) => panic!("Invalid {variable} ({value}): {why}"),
+        Ok(limit) => Some(limit),
+    }
+}
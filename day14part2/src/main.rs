@@ -2,6 +2,8 @@ use std::collections::HashSet;
 use std::fs::File;
 use std::io::{self, BufRead};
 
+use budget::{Budget, BudgetExceeded, CancellationToken};
+
 type Coordinates = (u64, u64);
 
 const SAND_ENTRY_POINT: Coordinates = (500, 0);
@@ -49,17 +51,24 @@ fn main() {
     let mut cave = Cave::new();
     read_input("input.txt", &mut cave);
     cave.mark_bottom();
-    let sand_units = count_sand_units(&mut cave);
+    let mut budget = Budget::from_env(CancellationToken::new());
+    let sand_units = match count_sand_units(&mut cave, &mut budget) {
+        Err(why) => panic!("Budget exceeded: {why}"),
+        Ok(c) => c,
+    };
     println!("Number of sand units = {sand_units}");
 }
 
-fn count_sand_units(cave: &mut Cave) -> usize {
+// Every sand unit that comes to rest grows the set of blocked tiles, so the
+// iteration limit also bounds its memory
+fn count_sand_units(cave: &mut Cave, budget: &mut Budget) -> Result<usize, BudgetExceeded> {
     let mut count: usize = 0;
     loop {
+        budget.check()?;
         count += 1;
         let rest_coordinates = find_rest_coordinates(cave);
         if rest_coordinates.0 == SAND_ENTRY_POINT.0 && rest_coordinates.1 == SAND_ENTRY_POINT.1 {
-            return count;
+            return Ok(count);
         }
         cave.block(&rest_coordinates);
     }
@@ -95,7 +104,7 @@ fn read_input(filename: &str, cave: &mut Cave) {
         .for_each(|line| mark_path(&line, cave));
 }
 
-fn mark_path(line: &String, cave: &mut Cave) {
+fn mark_path(line: &str, cave: &mut Cave) {
     let coordinates: Vec<Coordinates> = line.split(" -> ").map(parse_coordinates).collect();
     for i in 0..(coordinates.len() - 1) {
         mark_line(&coordinates[i], &coordinates[i + 1], cave);
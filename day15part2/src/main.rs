@@ -1,6 +1,8 @@
 use std::fs::File;
 use std::io::{self, BufRead};
 
+use budget::{Budget, BudgetExceeded, CancellationToken};
+
 const MIN: i64 = 0;
 const MAX: i64 = 4000000;
 //const MAX: i64 = 20;
@@ -29,20 +31,24 @@ impl Pair {
 
 fn main() {
     let pairs = read_input("input.txt");
-    let free_spot = find_free_spot(&pairs);
+    let mut budget = Budget::from_env(CancellationToken::new());
+    let free_spot = match find_free_spot(&pairs, &mut budget) {
+        Err(why) => panic!("Budget exceeded: {why}"),
+        Ok(s) => s,
+    };
     println!("Tuning frequency = {}", free_spot.0 * 4000000 + free_spot.1);
 }
 
-fn find_free_spot(pairs: &Vec<Pair>) -> Point {
+fn find_free_spot(pairs: &[Pair], budget: &mut Budget) -> Result<Point, BudgetExceeded> {
     for y in MIN..=MAX {
+        budget.check()?;
         let mut x = MIN;
         while x <= MAX {
             let pair = pairs
                 .iter()
-                .filter(|p| get_distance(&(x, y), &p.sensor) <= p.distance)
-                .next();
+                .find(|p| get_distance(&(x, y), &p.sensor) <= p.distance);
             let advance: i64 = match pair {
-                None => return (x, y),
+                None => return Ok((x, y)),
                 Some(p) => {
                     let distance_to_sensor = get_distance(&(x, y), &p.sensor);
                     let remaining_distance = (p.distance - distance_to_sensor) as i64;
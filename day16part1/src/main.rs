@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufRead};
 
+use budget::{Budget, BudgetExceeded, CancellationToken};
+
 struct Valve {
     rate: u32,
     tunnels: Vec<String>,
@@ -25,20 +27,32 @@ fn main() {
     let open_valves = ValveList::Last(useless_valve_names);
     let first_node = "AA".to_string();
     let trail_without_opening = ValveList::Last(vec![&first_node]);
-    let most_pressure_release =
-        get_most_pressure_release(&scan, 30, &first_node, &open_valves, &trail_without_opening);
+    let mut budget = Budget::from_env(CancellationToken::new());
+    let most_pressure_release = match get_most_pressure_release(
+        &scan,
+        &mut budget,
+        30,
+        &first_node,
+        &open_valves,
+        &trail_without_opening,
+    ) {
+        Err(why) => panic!("Budget exceeded: {why}"),
+        Ok(r) => r,
+    };
     println!("Most pressure release = {most_pressure_release}");
 }
 
 fn get_most_pressure_release(
     scan: &Scan,
+    budget: &mut Budget,
     minutes_left: u8,
     current_valve_name: &String,
     open_valves: &ValveList,
     trail_without_opening: &ValveList,
-) -> u32 {
+) -> Result<u32, BudgetExceeded> {
+    budget.check()?;
     if minutes_left == 0 {
-        return 0;
+        return Ok(0);
     }
     let mut most_pressure_release = 0;
     if !contains(open_valves, current_valve_name) {
@@ -46,34 +60,34 @@ fn get_most_pressure_release(
         let trail_without_opening = ValveList::Last(vec![current_valve_name]);
         let child_pressure_release = get_most_pressure_release(
             scan,
+            budget,
             minutes_left - 1,
             current_valve_name,
             &new_open_valves,
             &trail_without_opening,
-        );
+        )?;
         let extra_release = scan.get(current_valve_name).unwrap().rate * (minutes_left as u32 - 1);
         most_pressure_release = child_pressure_release + extra_release;
     }
-    let max_tunnels_pressure_release = scan
+    let tunnels = scan
         .get(current_valve_name)
         .unwrap()
         .tunnels
         .iter()
-        .filter(|tunnel| !contains(trail_without_opening, tunnel))
-        .map(|tunnel| {
-            let new_trail_without_opening = ValveList::Node(tunnel, &trail_without_opening);
-            get_most_pressure_release(
-                scan,
-                minutes_left - 1,
-                tunnel,
-                open_valves,
-                &new_trail_without_opening,
-            )
-        })
-        .max()
-        .unwrap_or(0);
-    most_pressure_release = cmp::max(most_pressure_release, max_tunnels_pressure_release);
-    most_pressure_release
+        .filter(|tunnel| !contains(trail_without_opening, tunnel));
+    for tunnel in tunnels {
+        let new_trail_without_opening = ValveList::Node(tunnel, trail_without_opening);
+        let tunnel_pressure_release = get_most_pressure_release(
+            scan,
+            budget,
+            minutes_left - 1,
+            tunnel,
+            open_valves,
+            &new_trail_without_opening,
+        )?;
+        most_pressure_release = cmp::max(most_pressure_release, tunnel_pressure_release);
+    }
+    Ok(most_pressure_release)
 }
 
 fn contains(valve_list: &ValveList, valve_name: &String) -> bool {
@@ -104,7 +118,7 @@ fn read_input(filename: &str) -> Scan {
 }
 
 // Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-fn parse_valve(line: &String) -> (String, Valve) {
+fn parse_valve(line: &str) -> (String, Valve) {
     let mut parts = line.split(&[';', '=', ',', ' ']);
     assert_part(parts.next(), "Valve");
     let name = parts.next().unwrap().to_string();
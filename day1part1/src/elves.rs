@@ -0,0 +1,121 @@
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+pub enum CalorieError {
+    Io(io::Error),
+    Negative {
+        elf: usize,
+        line: usize,
+        text: String,
+    },
+    NotANumber {
+        elf: usize,
+        line: usize,
+        text: String,
+    },
+    Overflow {
+        elf: usize,
+    },
+}
+
+// Elves and lines are reported 1-based, as they appear in the file
+impl fmt::Display for CalorieError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalorieError::Io(why) => write!(f, "couldn't read line: {why}"),
+            CalorieError::Negative { elf, line, text } => {
+                write!(f, "negative calories ({text}) for elf {elf} on line {line}")
+            }
+            CalorieError::NotANumber { elf, line, text } => {
+                write!(f, "not a number ({text}) for elf {elf} on line {line}")
+            }
+            CalorieError::Overflow { elf } => write!(f, "total calories of elf {elf} overflow"),
+        }
+    }
+}
+
+// Yields the calories of one elf at a time, so only a single inventory is kept in memory
+pub struct Elves<R: Read> {
+    lines: io::Lines<BufReader<R>>,
+    line_number: usize,
+    elf_number: usize,
+    done: bool,
+}
+
+impl<R: Read> Elves<R> {
+    pub fn new(reader: R) -> Self {
+        Elves {
+            lines: BufReader::new(reader).lines(),
+            line_number: 0,
+            elf_number: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R: Read> Iterator for Elves<R> {
+    type Item = Result<Vec<u64>, CalorieError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.elf_number += 1;
+        let mut calories = Vec::new();
+        loop {
+            let line = match self.lines.next() {
+                None => {
+                    self.done = true;
+                    return Some(Ok(calories));
+                }
+                Some(Err(why)) => {
+                    self.done = true;
+                    return Some(Err(CalorieError::Io(why)));
+                }
+                Some(Ok(line)) => line,
+            };
+            self.line_number += 1;
+            if line.is_empty() {
+                return Some(Ok(calories));
+            }
+            match parse_calories(&line, self.elf_number, self.line_number) {
+                Err(why) => {
+                    self.done = true;
+                    return Some(Err(why));
+                }
+                Ok(val) => calories.push(val),
+            }
+        }
+    }
+}
+
+fn parse_calories(line: &str, elf: usize, line_number: usize) -> Result<u64, CalorieError> {
+    if let Ok(val) = line.parse::<u64>() {
+        return Ok(val);
+    }
+    let text = line.to_string();
+    if line
+        .strip_prefix('-')
+        .is_some_and(|v| v.parse::<u64>().is_ok())
+    {
+        Err(CalorieError::Negative {
+            elf,
+            line: line_number,
+            text,
+        })
+    } else {
+        Err(CalorieError::NotANumber {
+            elf,
+            line: line_number,
+            text,
+        })
+    }
+}
+
+// index is 0-based
+pub fn total_calories(index: usize, calories: &[u64]) -> Result<u64, CalorieError> {
+    calories
+        .iter()
+        .try_fold(0u64, |sum, cals| sum.checked_add(*cals))
+        .ok_or(CalorieError::Overflow { elf: index + 1 })
+}
@@ -1,48 +1,82 @@
+use std::env;
 use std::fs::File;
-use std::io::{self, BufRead};
 
-fn main() {
-    let calories_per_elve = read_input("input.txt");
-    let most = get_most_calories(calories_per_elve);
-    println!("Answer: {most}");
-}
+mod elves;
+mod report;
+mod top_k;
 
-fn get_most_calories(calories_per_elve: Vec<Vec<i32>>) -> i32 {
-    return calories_per_elve.iter()
-        .map(|cals| cals.iter().sum())
-        .max()
-        .unwrap_or(0);
-}
+use elves::Elves;
+use report::Format;
 
-fn read_input(filename: &str) -> Vec<Vec<i32>> {
-    let lines = match read_lines(filename) {
-        Err(why) => panic!("couldn't open {filename}: {why}"),
-        Ok(lines) => lines,
-    };
+const DEFAULT_K: usize = 1;
 
-    let mut calories_per_elve = Vec::new();
-    let mut calories = Vec::new();
-    for line_result in lines {
-        let line = match line_result {
-            Err(why) => panic!("couldn't read line: {why}"),
-            Ok(line) => line,
-        };
-        if line == "" {
-            calories_per_elve.push(calories);
-            calories = Vec::new();
-        } else {
-            let val = match line.parse::<i32>() {
-                Err(why) => panic!("not a number ({line}): {why}"),
-                Ok(val) => val,
+const USAGE: &str = "Usage: [<number of elves>] | --report <table|csv>";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let k = match args
+        .iter()
+        .map(|a| a.as_str())
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        [] => DEFAULT_K,
+        ["--report", format] => {
+            let format = match *format {
+                "table" => Format::Table,
+                "csv" => Format::Csv,
+                _ => panic!("{USAGE}"),
             };
-            calories.push(val);
+            if let Err(why) = report::print_report(&read_input("input.txt"), format) {
+                panic!("{why}");
+            }
+            return;
         }
+        [k] => parse_k(k),
+        _ => panic!("{USAGE}"),
+    };
+    let top_elves = match top_k::get_top_k(Elves::new(open_input("input.txt")), k) {
+        Err(why) => panic!("{why}"),
+        Ok(t) => t,
+    };
+    top_elves
+        .elves
+        .iter()
+        .for_each(|elf| println!("Elf {} carries {} calories", elf.index + 1, elf.calories));
+    top_elves.ties.iter().for_each(|elf| {
+        println!(
+            "Elf {} also carries {} calories, but is left out of the top {k}",
+            elf.index + 1,
+            elf.calories
+        )
+    });
+    let most = top_elves
+        .elves
+        .iter()
+        .try_fold(0u64, |sum, elf| sum.checked_add(elf.calories));
+    match most {
+        None => panic!("Sum of the top {k} overflows"),
+        Some(most) => println!("Answer: {most}"),
     }
-    calories_per_elve.push(calories);
-    return calories_per_elve;
 }
 
-fn read_lines(filename: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
\ No newline at end of file
+fn parse_k(k: &str) -> usize {
+    match k.parse::<usize>() {
+        Err(why) => panic!("Invalid number of elves {k}: {why}"),
+        Ok(k) => k,
+    }
+}
+
+fn read_input(filename: &str) -> Vec<Vec<u64>> {
+    match Elves::new(open_input(filename)).collect() {
+        Err(why) => panic!("{why}"),
+        Ok(calories_per_elve) => calories_per_elve,
+    }
+}
+
+fn open_input(filename: &str) -> File {
+    match File::open(filename) {
+        Err(why) => panic!("couldn't open {filename}: {why}"),
+        Ok(file) => file,
+    }
+}
@@ -0,0 +1,186 @@
+use crate::elves::{self, CalorieError};
+
+const HISTOGRAM_BUCKETS: usize = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+pub enum Format {
+    Table,
+    Csv,
+}
+
+struct ElfSummary {
+    index: usize,
+    items: usize,
+    total: u64,
+}
+
+struct Bucket {
+    from: u64,
+    to: u64,
+    elves: usize,
+}
+
+struct Statistics {
+    elves: Vec<ElfSummary>,
+    min: u64,
+    max: u64,
+    mean: f64,
+    median: f64,
+    histogram: Vec<Bucket>,
+}
+
+pub fn print_report(calories_per_elve: &[Vec<u64>], format: Format) -> Result<(), CalorieError> {
+    let statistics = get_statistics(calories_per_elve)?;
+    match format {
+        Format::Table => print_table(&statistics),
+        Format::Csv => print_csv(&statistics),
+    }
+    Ok(())
+}
+
+fn get_statistics(calories_per_elve: &[Vec<u64>]) -> Result<Statistics, CalorieError> {
+    let elves = calories_per_elve
+        .iter()
+        .enumerate()
+        .map(|(index, cals)| {
+            Ok(ElfSummary {
+                index,
+                items: cals.len(),
+                total: elves::total_calories(index, cals)?,
+            })
+        })
+        .collect::<Result<Vec<ElfSummary>, CalorieError>>()?;
+    let mut totals: Vec<u64> = elves.iter().map(|e| e.total).collect();
+    totals.sort();
+    let min = totals.first().copied().unwrap_or(0);
+    let max = totals.last().copied().unwrap_or(0);
+    let mean = if totals.is_empty() {
+        0.0
+    } else {
+        totals.iter().map(|t| *t as f64).sum::<f64>() / totals.len() as f64
+    };
+    let middle = totals.len() / 2;
+    let median = match totals.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => (totals[middle - 1] as f64 + totals[middle] as f64) / 2.0,
+        _ => totals[middle] as f64,
+    };
+    let histogram = get_histogram(&totals, min, max);
+    Ok(Statistics {
+        elves,
+        min,
+        max,
+        mean,
+        median,
+        histogram,
+    })
+}
+
+// Equal-width buckets from min to max, the last one includes max
+fn get_histogram(totals: &[u64], min: u64, max: u64) -> Vec<Bucket> {
+    if totals.is_empty() {
+        return Vec::new();
+    }
+    let range = (max - min) as u128 + 1;
+    let bucket_count = (HISTOGRAM_BUCKETS as u128).min(range);
+    let width = range.div_ceil(bucket_count);
+    let mut buckets: Vec<Bucket> = (0..bucket_count)
+        .map(|i| {
+            let from = min as u128 + i * width;
+            Bucket {
+                from: from as u64,
+                to: (from + width - 1).min(max as u128) as u64,
+                elves: 0,
+            }
+        })
+        .collect();
+    for total in totals {
+        let bucket = ((total - min) as u128 / width) as usize;
+        buckets[bucket].elves += 1;
+    }
+    buckets
+}
+
+fn print_table(statistics: &Statistics) {
+    println!("{:>6} {:>6} {:>10}", "Elf", "Items", "Calories");
+    for elf in &statistics.elves {
+        let note = if elf.items == 0 {
+            "  empty inventory"
+        } else {
+            ""
+        };
+        println!(
+            "{:>6} {:>6} {:>10}{note}",
+            elf.index + 1,
+            elf.items,
+            elf.total
+        );
+    }
+    println!();
+    println!("Elves:  {}", statistics.elves.len());
+    println!("Min:    {}", statistics.min);
+    println!("Max:    {}", statistics.max);
+    println!("Mean:   {:.1}", statistics.mean);
+    println!("Median: {:.1}", statistics.median);
+    println!();
+    let most_elves = statistics
+        .histogram
+        .iter()
+        .map(|b| b.elves)
+        .max()
+        .unwrap_or(0);
+    for bucket in &statistics.histogram {
+        let bar_length = if most_elves == 0 {
+            0
+        } else {
+            (bucket.elves * HISTOGRAM_WIDTH).div_ceil(most_elves)
+        };
+        let line = format!(
+            "{:>10} - {:<10} {:>6} {}",
+            bucket.from,
+            bucket.to,
+            bucket.elves,
+            "#".repeat(bar_length)
+        );
+        println!("{}", line.trim_end());
+    }
+    let empty: Vec<String> = statistics
+        .elves
+        .iter()
+        .filter(|e| e.items == 0)
+        .map(|e| (e.index + 1).to_string())
+        .collect();
+    if !empty.is_empty() {
+        println!();
+        println!(
+            "Elves with an empty inventory (consecutive blank lines?): {}",
+            empty.join(", ")
+        );
+    }
+}
+
+// Three tables separated by a blank line: elves, statistics and histogram
+fn print_csv(statistics: &Statistics) {
+    println!("elf,items,calories,empty");
+    for elf in &statistics.elves {
+        println!(
+            "{},{},{},{}",
+            elf.index + 1,
+            elf.items,
+            elf.total,
+            elf.items == 0
+        );
+    }
+    println!();
+    println!("statistic,value");
+    println!("elves,{}", statistics.elves.len());
+    println!("min,{}", statistics.min);
+    println!("max,{}", statistics.max);
+    println!("mean,{}", statistics.mean);
+    println!("median,{}", statistics.median);
+    println!();
+    println!("from,to,elves");
+    for bucket in &statistics.histogram {
+        println!("{},{},{}", bucket.from, bucket.to, bucket.elves);
+    }
+}
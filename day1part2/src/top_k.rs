@@ -0,0 +1,86 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::elves::{self, CalorieError};
+
+pub struct Elf {
+    pub index: usize,
+    pub calories: u64,
+}
+
+pub struct TopElves {
+    // Most calories first
+    pub elves: Vec<Elf>,
+    // Elves left out that carry as much as the last elf in the top
+    pub ties: Vec<Elf>,
+}
+
+// Keeps the k largest totals in a min-heap, so the smallest one is evicted first.
+// On equal totals the earlier elf is kept.
+pub struct TopK {
+    k: usize,
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+    most_evicted: Option<(u64, Vec<usize>)>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+            most_evicted: None,
+        }
+    }
+
+    pub fn add(&mut self, index: usize, calories: u64) {
+        self.heap.push(Reverse((calories, Reverse(index))));
+        if self.heap.len() > self.k {
+            if let Some(Reverse((calories, Reverse(index)))) = self.heap.pop() {
+                self.evicted(index, calories);
+            }
+        }
+    }
+
+    // Only the evicted elves with the highest total can tie with the top
+    fn evicted(&mut self, index: usize, calories: u64) {
+        match &mut self.most_evicted {
+            Some((most, indexes)) if *most == calories => indexes.push(index),
+            Some((most, _)) if *most > calories => {}
+            _ => self.most_evicted = Some((calories, vec![index])),
+        }
+    }
+
+    pub fn into_top_elves(self) -> TopElves {
+        let elves: Vec<Elf> = self
+            .heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(index)))| Elf { index, calories })
+            .collect();
+        let ties = match (elves.last(), self.most_evicted) {
+            (Some(last), Some((most, mut indexes))) if last.calories == most => {
+                indexes.sort();
+                indexes
+                    .into_iter()
+                    .map(|index| Elf {
+                        index,
+                        calories: most,
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        TopElves { elves, ties }
+    }
+}
+
+pub fn get_top_k<I>(calories_per_elve: I, k: usize) -> Result<TopElves, CalorieError>
+where
+    I: Iterator<Item = Result<Vec<u64>, CalorieError>>,
+{
+    let mut top_k = TopK::new(k);
+    for (index, cals) in calories_per_elve.enumerate() {
+        top_k.add(index, elves::total_calories(index, &cals?)?);
+    }
+    Ok(top_k.into_top_elves())
+}
@@ -0,0 +1,135 @@
+use crate::rules::{Interpretation, Outcome, Round, Rules, Shape};
+
+// Bots only see the opponent's earlier shapes
+#[derive(Clone, Copy)]
+enum Bot {
+    AlwaysFirst,
+    CopyLast,
+    BeatLast,
+    FrequencyCounter,
+}
+
+const BOTS: [Bot; 4] = [
+    Bot::AlwaysFirst,
+    Bot::CopyLast,
+    Bot::BeatLast,
+    Bot::FrequencyCounter,
+];
+
+struct Strategy {
+    name: String,
+    score: u32,
+}
+
+pub fn print_analysis(rules: &Rules, rounds: &[Round], interpretation: Interpretation) {
+    let opponents: Vec<Shape> = rounds.iter().map(|r| r.opponent).collect();
+    let best_scores: Vec<u32> = opponents
+        .iter()
+        .map(|opponent| get_max_score(rules, *opponent))
+        .collect();
+    let max_score: u32 = best_scores.iter().sum();
+
+    println!(
+        "{:>6}  {:<10} {:<10} {:>6} {:>6} {:>6}",
+        "Round", "Opponent", "Guide", "Score", "Best", "Regret"
+    );
+    let mut guide_score = 0;
+    for (i, round) in rounds.iter().enumerate() {
+        let me = rules.my_shape(round, interpretation);
+        let score = rules.score(me, round.opponent);
+        guide_score += score;
+        println!(
+            "{:>6}  {:<10} {:<10} {:>6} {:>6} {:>6}",
+            i + 1,
+            rules.name(round.opponent),
+            rules.name(me),
+            score,
+            best_scores[i],
+            best_scores[i] - score
+        );
+    }
+    println!();
+
+    let mut strategies = vec![
+        Strategy {
+            name: "guide".to_string(),
+            score: guide_score,
+        },
+        Strategy {
+            name: "optimal".to_string(),
+            score: max_score,
+        },
+    ];
+    for bot in BOTS {
+        let score = get_bot_moves(rules, bot, &opponents)
+            .iter()
+            .zip(opponents.iter())
+            .map(|(me, opponent)| rules.score(*me, *opponent))
+            .sum();
+        strategies.push(Strategy {
+            name: get_bot_name(rules, bot),
+            score,
+        });
+    }
+    println!("{:<20} {:>8} {:>8}", "Strategy", "Score", "Regret");
+    strategies
+        .iter()
+        .for_each(|s| println!("{:<20} {:>8} {:>8}", s.name, s.score, max_score - s.score));
+    println!();
+    println!("Maximum achievable score is {max_score}");
+}
+
+pub fn get_max_score(rules: &Rules, opponent: Shape) -> u32 {
+    rules
+        .shapes()
+        .map(|me| rules.score(me, opponent))
+        .max()
+        .unwrap_or(0)
+}
+
+fn get_bot_name(rules: &Rules, bot: Bot) -> String {
+    match bot {
+        Bot::AlwaysFirst => match rules.shapes().next() {
+            None => "always-first".to_string(),
+            Some(first) => format!("always-{}", rules.name(first).to_lowercase()),
+        },
+        Bot::CopyLast => "copy-last".to_string(),
+        Bot::BeatLast => "beat-last".to_string(),
+        Bot::FrequencyCounter => "frequency-counter".to_string(),
+    }
+}
+
+fn get_bot_moves(rules: &Rules, bot: Bot, opponents: &[Shape]) -> Vec<Shape> {
+    let first = match rules.shapes().next() {
+        None => return Vec::new(),
+        Some(s) => s,
+    };
+    let beat = |shape: Shape| {
+        rules
+            .shape_for_outcome(shape, Outcome::Win)
+            .unwrap_or(first)
+    };
+    let mut counts = vec![0usize; rules.shapes().count()];
+    let mut moves = Vec::with_capacity(opponents.len());
+    for (i, opponent) in opponents.iter().enumerate() {
+        let last = if i == 0 { None } else { Some(opponents[i - 1]) };
+        let me = match (bot, last) {
+            (Bot::AlwaysFirst, _) | (_, None) => first,
+            (Bot::CopyLast, Some(l)) => l,
+            (Bot::BeatLast, Some(l)) => beat(l),
+            // The earliest declared shape wins a tie in frequency
+            (Bot::FrequencyCounter, Some(_)) => {
+                let mut most_frequent = first;
+                for shape in rules.shapes() {
+                    if counts[rules.index(shape)] > counts[rules.index(most_frequent)] {
+                        most_frequent = shape;
+                    }
+                }
+                beat(most_frequent)
+            }
+        };
+        moves.push(me);
+        counts[rules.index(*opponent)] += 1;
+    }
+    moves
+}
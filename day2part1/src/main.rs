@@ -1,95 +1,104 @@
+use std::env;
 use std::fs::File;
 use std::io::{self, BufRead};
 
-const SHAPE_ROCK: i32 = 1;
-const SHAPE_PAPER: i32 = 2;
-const SHAPE_SCISSORS: i32 = 3;
+mod analysis;
+mod rules;
 
-struct Round {
-    player1: i32,
-    player2: i32,
-}
+use rules::{Interpretation, Round, Rules};
 
-fn main() {
-    let rounds = read_input("input.txt");
-    let score: i32 = rounds.iter().map(|round| get_score(&round)).sum();
-    println!("Total score is {score}")
-}
+const DEFAULT_INTERPRETATION: Interpretation = Interpretation::MyMove;
 
-fn get_score(round: &Round) -> i32 {
-    let score = match round.player1 {
-        SHAPE_ROCK => match round.player2 {
-            SHAPE_ROCK => 3,
-            SHAPE_PAPER => 0,
-            SHAPE_SCISSORS => 6,
-            _ => panic!("Unknown shape {}", round.player2),
-        },
-        SHAPE_PAPER => match round.player2 {
-            SHAPE_ROCK => 6,
-            SHAPE_PAPER => 3,
-            SHAPE_SCISSORS => 0,
-            _ => panic!("Unknown shape {}", round.player2),
-        },
-        SHAPE_SCISSORS => match round.player2 {
-            SHAPE_ROCK => 0,
-            SHAPE_PAPER => 6,
-            SHAPE_SCISSORS => 3,
-            _ => panic!("Unknown shape {}", round.player2),
-        },
-        _ => panic!("Unknown shape {}", round.player1),
+const USAGE: &str = "Usage: [move|outcome] [--rules <file>] [--analyse]";
+
+fn main() {
+    let mut interpretation = DEFAULT_INTERPRETATION;
+    let mut rules_file: Option<String> = None;
+    let mut analyse = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "move" => interpretation = Interpretation::MyMove,
+            "outcome" => interpretation = Interpretation::DesiredOutcome,
+            "--rules" => rules_file = args.next(),
+            "--analyse" => analyse = true,
+            _ => panic!("{USAGE}"),
+        }
+    }
+    let rules = match rules_file {
+        None => Rules::parse(rules::ROCK_PAPER_SCISSORS),
+        Some(f) => Rules::load(&f),
     };
-    return round.player1 + score;
+    let rules = match rules {
+        Err(why) => panic!("Invalid rules: {why}"),
+        Ok(r) => r,
+    };
+    let rounds = read_input("input.txt", &rules, interpretation);
+    if analyse {
+        analysis::print_analysis(&rules, &rounds, interpretation);
+        return;
+    }
+    let score: u32 = rounds
+        .iter()
+        .map(|round| rules.get_score(round, interpretation))
+        .sum();
+    println!("Total score is {score}")
 }
 
-fn parse_round(input: &String) -> Round {
+// The second column must be a letter of the chosen interpretation, not just of either one
+fn parse_round(
+    input: &str,
+    rules: &Rules,
+    interpretation: Interpretation,
+) -> Result<Round, String> {
     let mut chars = input.chars();
     let player2_char = match chars.next() {
-        None => panic!("Expected player 2's move"),
+        None => return Err("Expected player 2's move".to_string()),
         Some(a) => a,
     };
     let space = match chars.next() {
-        None => panic!("Expected space"),
+        None => return Err("Expected space".to_string()),
         Some(a) => a,
     };
     if space != ' ' {
-        panic!("Expected space but found {space}");
+        return Err(format!("Expected space but found {space}"));
     }
-    let player1_char = match chars.next() {
-        None => panic!("Expected player 1's move"),
+    let column_char = match chars.next() {
+        None => return Err("Expected player 1's move or result".to_string()),
         Some(a) => a,
     };
 
-     let player2 = match player2_char {
-        'A' => SHAPE_ROCK,
-        'B' => SHAPE_PAPER,
-        'C' => SHAPE_SCISSORS,
-        _ => panic!("Invalid char for player 2 {player2_char}")
-     };
-     let player1 = match player1_char {
-        'X' => SHAPE_ROCK,
-        'Y' => SHAPE_PAPER,
-        'Z' => SHAPE_SCISSORS,
-        _ => panic!("Invalid char for player 1 {player1_char}")
-     };
-     return Round {
-        player1,
-        player2,
-     };
+    let opponent = match rules.opponent(player2_char) {
+        None => return Err(format!("Invalid char for player 2 {player2_char}")),
+        Some(shape) => shape,
+    };
+    if !rules.is_second_column(column_char, interpretation) {
+        return Err(format!("Invalid char for second column {column_char}"));
+    }
+    Ok(Round {
+        opponent,
+        second_column: column_char,
+    })
 }
 
-fn read_input(filename: &str) -> Vec<Round> {
+fn read_input(filename: &str, rules: &Rules, interpretation: Interpretation) -> Vec<Round> {
     let lines = match read_lines(filename) {
         Err(why) => panic!("couldn't open {filename}: {why}"),
         Ok(lines) => lines,
     };
-    return lines.map(|line|
-        match line {
+    lines
+        .enumerate()
+        .map(|(i, line)| match line {
             Err(why) => panic!("couldn't read line: {why}"),
-            Ok(l) => parse_round(&l),
-        }).collect();
+            Ok(l) => match parse_round(&l, rules, interpretation) {
+                Err(why) => panic!("Line {}: {why}", i + 1),
+                Ok(round) => round,
+            },
+        })
+        .collect()
 }
 
 fn read_lines(filename: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
     let file = File::open(filename)?;
     Ok(io::BufReader::new(file).lines())
-}
\ No newline at end of file
+}
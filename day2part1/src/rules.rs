@@ -0,0 +1,273 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+// Rules files list one declaration per line, '#' starts a comment:
+//   shape <name> <score>
+//   beats <winner> <loser>...
+//   outcome <lose|draw|win> <score>
+//   opponent <letter> <shape>
+//   move <letter> <shape>
+//   result <letter> <lose|draw|win>
+pub const ROCK_PAPER_SCISSORS: &str = "
+shape Rock 1
+shape Paper 2
+shape Scissors 3
+beats Rock Scissors
+beats Paper Rock
+beats Scissors Paper
+outcome lose 0
+outcome draw 3
+outcome win 6
+opponent A Rock
+opponent B Paper
+opponent C Scissors
+move X Rock
+move Y Paper
+move Z Scissors
+result X lose
+result Y draw
+result Z win
+";
+
+// An index into the shapes of the rules
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape(usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+#[derive(Clone, Copy)]
+pub enum Interpretation {
+    MyMove,
+    DesiredOutcome,
+}
+
+pub struct Round {
+    pub opponent: Shape,
+    // Read as either a shape or an outcome, depending on the interpretation
+    pub second_column: char,
+}
+
+struct ShapeRule {
+    name: String,
+    score: u32,
+}
+
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+    // (winner, loser)
+    beats: HashSet<(Shape, Shape)>,
+    outcome_scores: HashMap<Outcome, u32>,
+    opponent_letters: HashMap<char, Shape>,
+    move_letters: HashMap<char, Shape>,
+    result_letters: HashMap<char, Outcome>,
+}
+
+impl Outcome {
+    pub fn of(rules: &Rules, me: Shape, opponent: Shape) -> Outcome {
+        if rules.beats.contains(&(me, opponent)) {
+            Outcome::Win
+        } else if rules.beats.contains(&(opponent, me)) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    fn parse(name: &str) -> Option<Outcome> {
+        match name {
+            "lose" => Some(Outcome::Lose),
+            "draw" => Some(Outcome::Draw),
+            "win" => Some(Outcome::Win),
+            _ => None,
+        }
+    }
+}
+
+impl Rules {
+    pub fn load(filename: &str) -> Result<Rules, String> {
+        match fs::read_to_string(filename) {
+            Err(why) => Err(format!("couldn't read {filename}: {why}")),
+            Ok(text) => Rules::parse(&text).map_err(|why| format!("{filename}: {why}")),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Rules, String> {
+        let mut rules = Rules {
+            shapes: Vec::new(),
+            beats: HashSet::new(),
+            outcome_scores: HashMap::new(),
+            opponent_letters: HashMap::new(),
+            move_letters: HashMap::new(),
+            result_letters: HashMap::new(),
+        };
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("");
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if !words.is_empty() {
+                rules
+                    .parse_declaration(&words)
+                    .map_err(|why| format!("line {}: {why}", i + 1))?;
+            }
+        }
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    fn parse_declaration(&mut self, words: &[&str]) -> Result<(), String> {
+        match words {
+            ["shape", name, score] => {
+                if self.find_shape(name).is_ok() {
+                    return Err(format!("Shape {name} is declared twice"));
+                }
+                let score = parse_score(score)?;
+                self.shapes.push(ShapeRule {
+                    name: name.to_string(),
+                    score,
+                });
+            }
+            ["beats", winner, losers @ ..] if !losers.is_empty() => {
+                let winner = self.find_shape(winner)?;
+                for loser in losers {
+                    let loser = self.find_shape(loser)?;
+                    if winner == loser {
+                        return Err(format!("{} cannot beat itself", self.name(winner)));
+                    }
+                    if self.beats.contains(&(loser, winner)) {
+                        return Err(format!(
+                            "{} and {} beat each other",
+                            self.name(winner),
+                            self.name(loser)
+                        ));
+                    }
+                    self.beats.insert((winner, loser));
+                }
+            }
+            ["outcome", outcome, score] => {
+                let outcome = parse_outcome(outcome)?;
+                self.outcome_scores.insert(outcome, parse_score(score)?);
+            }
+            ["opponent", letter, shape] => {
+                let shape = self.find_shape(shape)?;
+                self.opponent_letters.insert(parse_letter(letter)?, shape);
+            }
+            ["move", letter, shape] => {
+                let shape = self.find_shape(shape)?;
+                self.move_letters.insert(parse_letter(letter)?, shape);
+            }
+            ["result", letter, outcome] => {
+                let outcome = parse_outcome(outcome)?;
+                self.result_letters.insert(parse_letter(letter)?, outcome);
+            }
+            _ => return Err(format!("Invalid declaration '{}'", words.join(" "))),
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.shapes.is_empty() {
+            return Err("No shapes declared".to_string());
+        }
+        for outcome in [Outcome::Lose, Outcome::Draw, Outcome::Win] {
+            if !self.outcome_scores.contains_key(&outcome) {
+                return Err("Every outcome needs a score".to_string());
+            }
+        }
+        for opponent in self.opponent_letters.values() {
+            for outcome in self.result_letters.values() {
+                if self.shape_for_outcome(*opponent, *outcome).is_none() {
+                    return Err(format!(
+                        "No shape reaches every desired outcome against {}",
+                        self.name(*opponent)
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.shapes.len()).map(Shape)
+    }
+
+    pub fn index(&self, shape: Shape) -> usize {
+        shape.0
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0].name
+    }
+
+    pub fn opponent(&self, letter: char) -> Option<Shape> {
+        self.opponent_letters.get(&letter).copied()
+    }
+
+    pub fn is_second_column(&self, letter: char, interpretation: Interpretation) -> bool {
+        match interpretation {
+            Interpretation::MyMove => self.move_letters.contains_key(&letter),
+            Interpretation::DesiredOutcome => self.result_letters.contains_key(&letter),
+        }
+    }
+
+    // With more than three shapes several can reach the outcome; the first declared one is used
+    pub fn shape_for_outcome(&self, opponent: Shape, outcome: Outcome) -> Option<Shape> {
+        self.shapes()
+            .find(|me| Outcome::of(self, *me, opponent) == outcome)
+    }
+
+    pub fn get_score(&self, round: &Round, interpretation: Interpretation) -> u32 {
+        self.score(self.my_shape(round, interpretation), round.opponent)
+    }
+
+    pub fn my_shape(&self, round: &Round, interpretation: Interpretation) -> Shape {
+        match interpretation {
+            Interpretation::MyMove => match self.move_letters.get(&round.second_column) {
+                None => panic!("No move for {}", round.second_column),
+                Some(shape) => *shape,
+            },
+            Interpretation::DesiredOutcome => {
+                let outcome = match self.result_letters.get(&round.second_column) {
+                    None => panic!("No result for {}", round.second_column),
+                    Some(outcome) => *outcome,
+                };
+                match self.shape_for_outcome(round.opponent, outcome) {
+                    None => panic!("No shape reaches the desired outcome"),
+                    Some(shape) => shape,
+                }
+            }
+        }
+    }
+
+    pub fn score(&self, me: Shape, opponent: Shape) -> u32 {
+        self.shapes[me.0].score + self.outcome_scores[&Outcome::of(self, me, opponent)]
+    }
+
+    fn find_shape(&self, name: &str) -> Result<Shape, String> {
+        match self.shapes.iter().position(|s| s.name == name) {
+            None => Err(format!("Unknown shape {name}")),
+            Some(i) => Ok(Shape(i)),
+        }
+    }
+}
+
+fn parse_score(score: &str) -> Result<u32, String> {
+    score
+        .parse::<u32>()
+        .map_err(|why| format!("Invalid score {score}: {why}"))
+}
+
+fn parse_outcome(outcome: &str) -> Result<Outcome, String> {
+    Outcome::parse(outcome).ok_or(format!("Expected lose, draw or win but found {outcome}"))
+}
+
+fn parse_letter(letter: &str) -> Result<char, String> {
+    let mut chars = letter.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!("Expected a single letter but found {letter}")),
+    }
+}
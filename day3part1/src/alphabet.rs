@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::fs;
+
+// The items in priority order, the first one has priority 1
+pub struct Alphabet {
+    items: Vec<char>,
+    priorities: HashMap<char, u32>,
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        match Alphabet::parse(&('a'..='z').chain('A'..='Z').collect::<String>()) {
+            Err(why) => panic!("Invalid default alphabet: {why}"),
+            Ok(alphabet) => alphabet,
+        }
+    }
+}
+
+impl Alphabet {
+    pub fn load(filename: &str) -> Result<Alphabet, String> {
+        match fs::read_to_string(filename) {
+            Err(why) => Err(format!("couldn't read {filename}: {why}")),
+            Ok(text) => Alphabet::parse(&text).map_err(|why| format!("{filename}: {why}")),
+        }
+    }
+
+    // Whitespace only separates items, so it can't be an item itself
+    pub fn parse(text: &str) -> Result<Alphabet, String> {
+        let mut alphabet = Alphabet {
+            items: Vec::new(),
+            priorities: HashMap::new(),
+        };
+        for item in text.chars().filter(|c| !c.is_whitespace()) {
+            let priority = alphabet.items.len() as u32 + 1;
+            if alphabet.priorities.insert(item, priority).is_some() {
+                return Err(format!("Item {item} is listed twice"));
+            }
+            alphabet.items.push(item);
+        }
+        if alphabet.items.is_empty() {
+            return Err("No items listed".to_string());
+        }
+        Ok(alphabet)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn priority(&self, item: char) -> Option<u32> {
+        self.priorities.get(&item).copied()
+    }
+
+    pub fn item(&self, priority: u32) -> char {
+        self.items[priority as usize - 1]
+    }
+}
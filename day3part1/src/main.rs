@@ -1,65 +1,111 @@
+use std::env;
 use std::fs::File;
 use std::io::{self, BufRead};
 
+mod alphabet;
+mod item_set;
+
+use alphabet::Alphabet;
+use item_set::ItemSet;
+
+const USAGE: &str = "Usage: [--alphabet <file>]";
+
 struct RuckSack {
-    compartment1: String,
-    compartment2: String,
+    items: String,
+    compartment1: ItemSet,
+    compartment2: ItemSet,
 }
 
 fn main() {
-    let rucksacks = read_input("input.txt");
-    let priorities_sum: u32 = rucksacks
-        .iter()
-        .map(|r| get_incorrect_item_priority(r))
-        .sum();
+    let mut alphabet_file: Option<String> = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--alphabet" => alphabet_file = args.next(),
+            _ => panic!("{USAGE}"),
+        }
+    }
+    let alphabet = match alphabet_file {
+        None => Alphabet::default(),
+        Some(f) => match Alphabet::load(&f) {
+            Err(why) => panic!("Invalid alphabet: {why}"),
+            Ok(a) => a,
+        },
+    };
+    let rucksacks = read_input("input.txt", &alphabet);
+    let mut priorities_sum = 0;
+    let mut diagnosed_rucksacks = 0;
+    for (i, rucksack) in rucksacks.iter().enumerate() {
+        match get_incorrect_item_priority(rucksack, i + 1, &alphabet) {
+            None => diagnosed_rucksacks += 1,
+            Some(priority) => priorities_sum += priority,
+        }
+    }
+    if diagnosed_rucksacks > 0 {
+        println!("{diagnosed_rucksacks} rucksacks with several incorrect items are left out");
+    }
     println!("Sum of priorities is {priorities_sum}");
 }
 
-fn get_incorrect_item_priority(rucksack: &RuckSack) -> u32 {
-    println!("Rucksack {} - {}", rucksack.compartment1, rucksack.compartment2);
-    let incorrect_item = rucksack
-        .compartment1
-        .chars()
-        .find(|item| rucksack.compartment2.contains(*item));
-    let priority = match incorrect_item {
-        None => panic!(
-            "No incorrect item found in {} - {}",
-            rucksack.compartment1, rucksack.compartment2
+// Exactly one item type should be in both compartments, rucksacks with more are left out
+fn get_incorrect_item_priority(
+    rucksack: &RuckSack,
+    line_number: usize,
+    alphabet: &Alphabet,
+) -> Option<u32> {
+    println!("Rucksack {}", rucksack.items);
+    let incorrect_items = &rucksack.compartment1 & &rucksack.compartment2;
+    let items: String = incorrect_items.items(alphabet).collect();
+    match items.chars().count() {
+        0 => panic!(
+            "Line {line_number}: no incorrect item found in {}",
+            rucksack.items
         ),
-        Some(item) => {
-            println!("  Incorrect item is {item}");
-            if item >= 'a' && item <= 'z' {
-                item as u32 - 'a' as u32 + 1u32
-            } else if item >= 'A' && item <= 'Z' {
-                item as u32 - 'A' as u32 + 27u32
-            } else {
-                panic!("Invalid item {item}")
-            }
+        1 => {
+            println!("  Incorrect item is {items}");
+            let priority = incorrect_items.priority_sum();
+            println!("  Priority is {priority}");
+            Some(priority)
         }
-    };
-    println!("  Priority is {priority}");
-    return priority;
+        count => {
+            println!("  Line {line_number} has {count} incorrect items: {items}");
+            None
+        }
+    }
 }
 
-fn parse_rucksack(input: &String) -> RuckSack {
-    let middle = input.len() / 2;
-    return RuckSack {
-        compartment1: String::from(&input[..middle]),
-        compartment2: String::from(&input[middle..]),
+// Compartments hold the same number of items, whatever their encoded length
+fn parse_rucksack(input: &str, alphabet: &Alphabet) -> Result<RuckSack, String> {
+    let count = input.chars().count();
+    if !count.is_multiple_of(2) {
+        return Err(format!("Rucksack has an odd number of items ({count})"));
+    }
+    let middle = match input.char_indices().nth(count / 2) {
+        None => input.len(),
+        Some((i, _)) => i,
     };
+    Ok(RuckSack {
+        items: input.to_string(),
+        compartment1: ItemSet::from_items(&input[..middle], alphabet)?,
+        compartment2: ItemSet::from_items(&input[middle..], alphabet)?,
+    })
 }
 
-fn read_input(filename: &str) -> Vec<RuckSack> {
+fn read_input(filename: &str, alphabet: &Alphabet) -> Vec<RuckSack> {
     let lines = match read_lines(filename) {
         Err(why) => panic!("couldn't open {filename}: {why}"),
         Ok(lines) => lines,
     };
-    return lines
-        .map(|line| match line {
+    lines
+        .enumerate()
+        .map(|(i, line)| match line {
             Err(why) => panic!("couldn't read line: {why}"),
-            Ok(l) => parse_rucksack(&l),
+            Ok(l) => match parse_rucksack(&l, alphabet) {
+                Err(why) => panic!("Line {}: {why}", i + 1),
+                Ok(rucksack) => rucksack,
+            },
         })
-        .collect();
+        .collect()
 }
 
 fn read_lines(filename: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
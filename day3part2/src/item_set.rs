@@ -0,0 +1,81 @@
+use std::ops::{BitAnd, BitOr};
+
+use crate::alphabet::Alphabet;
+
+// One bit per item, the bit index being the item's priority in the alphabet
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct ItemSet {
+    words: Vec<u64>,
+}
+
+impl ItemSet {
+    pub fn from_items(items: &str, alphabet: &Alphabet) -> Result<ItemSet, String> {
+        let mut set = ItemSet {
+            words: vec![0; alphabet.len() / 64 + 1],
+        };
+        for item in items.chars() {
+            match alphabet.priority(item) {
+                None => return Err(format!("Item '{item}' is not in the alphabet")),
+                Some(priority) => set.insert(priority),
+            }
+        }
+        Ok(set)
+    }
+
+    fn insert(&mut self, priority: u32) {
+        self.words[priority as usize / 64] |= 1 << (priority % 64);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    pub fn priorities(&self) -> impl Iterator<Item = u32> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, word)| {
+            (0..64)
+                .filter(move |bit| word & 1 << bit != 0)
+                .map(move |bit| i as u32 * 64 + bit)
+        })
+    }
+
+    pub fn items<'a>(&'a self, alphabet: &'a Alphabet) -> impl Iterator<Item = char> + 'a {
+        self.priorities().map(|priority| alphabet.item(priority))
+    }
+
+    pub fn priority_sum(&self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+impl BitAnd for &ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: &ItemSet) -> ItemSet {
+        ItemSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+}
+
+impl BitOr for &ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: &ItemSet) -> ItemSet {
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = longer.words.clone();
+        words
+            .iter_mut()
+            .zip(&shorter.words)
+            .for_each(|(a, b)| *a |= b);
+        ItemSet { words }
+    }
+}
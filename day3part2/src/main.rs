@@ -1,65 +1,108 @@
+use std::env;
 use std::fs::File;
 use std::io::{self, BufRead};
 
+mod alphabet;
+mod item_set;
+
+use alphabet::Alphabet;
+use item_set::ItemSet;
+
+const DEFAULT_GROUP_SIZE: usize = 3;
+
+const USAGE: &str = "Usage: [group size] [--alphabet <file>]";
+
 struct Group {
-    rucksacks: [String; 3],
+    rucksacks: Vec<ItemSet>,
 }
 
-fn main() {
-    let groups = read_input("input.txt");
-    let priorities_sum: u32 = groups.iter().map(get_group_badge_priority).sum();
-    println!("Sum of priorities is {priorities_sum}");
+impl Group {
+    fn get_shared_items(&self) -> ItemSet {
+        match self.rucksacks.split_first() {
+            None => ItemSet::default(),
+            Some((first, rest)) => rest.iter().fold(first.clone(), |shared, r| &shared & r),
+        }
+    }
 }
 
-fn get_group_badge_priority(group: &Group) -> u32 {
-    let badge = group.rucksacks[0]
-        .chars()
-        .find(|item| group.rucksacks[1].contains(*item) && group.rucksacks[2].contains(*item));
-    return match badge {
-        None => panic!(
-            "No badge found in {} - {} - {}",
-            group.rucksacks[0], group.rucksacks[1], group.rucksacks[2],
-        ),
-        Some(item) => {
-            println!("  Badge is {item}");
-            if item >= 'a' && item <= 'z' {
-                item as u32 - 'a' as u32 + 1u32
-            } else if item >= 'A' && item <= 'Z' {
-                item as u32 - 'A' as u32 + 27u32
-            } else {
-                panic!("Invalid item {item}")
-            }
+fn main() {
+    let mut group_size = DEFAULT_GROUP_SIZE;
+    let mut alphabet_file: Option<String> = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--alphabet" => alphabet_file = args.next(),
+            s => match s.parse::<usize>() {
+                Ok(size) if size > 0 => group_size = size,
+                _ => panic!("{USAGE}"),
+            },
         }
+    }
+    let alphabet = match alphabet_file {
+        None => Alphabet::default(),
+        Some(f) => match Alphabet::load(&f) {
+            Err(why) => panic!("Invalid alphabet: {why}"),
+            Ok(a) => a,
+        },
     };
+    let groups = read_input("input.txt", group_size, &alphabet);
+    let mut priorities_sum = 0;
+    let mut diagnosed_groups = 0;
+    for (i, group) in groups.iter().enumerate() {
+        let number = i + 1;
+        let shared_items = group.get_shared_items();
+        let items: String = shared_items.items(&alphabet).collect();
+        println!("Group {number} shares [{items}]");
+        if group.rucksacks.len() < group_size {
+            println!(
+                "  Group {number} only has {} of {group_size} elves",
+                group.rucksacks.len()
+            );
+            diagnosed_groups += 1;
+            continue;
+        }
+        if shared_items.is_empty() {
+            println!("  Group {number} has no badge");
+        } else if items.chars().count() == 1 {
+            println!("  Badge is {items}");
+            priorities_sum += shared_items.priority_sum();
+            continue;
+        } else {
+            println!(
+                "  Group {number} has {} candidate badges: {items}",
+                items.chars().count()
+            );
+        }
+        diagnosed_groups += 1;
+    }
+    if diagnosed_groups > 0 {
+        println!("{diagnosed_groups} groups without a single badge are left out");
+    }
+    println!("Sum of priorities is {priorities_sum}");
 }
 
-fn read_input(filename: &str) -> Vec<Group> {
+// The last group is kept even when it has fewer elves, so it can be diagnosed
+fn read_input(filename: &str, group_size: usize, alphabet: &Alphabet) -> Vec<Group> {
     let lines = match read_lines(filename) {
         Err(why) => panic!("couldn't open {filename}: {why}"),
         Ok(lines) => lines,
     };
-    let mut unwrapped_lines = lines.map(|line| match line {
-        Err(why) => panic!("couldn't read line: {why}"),
-        Ok(l) => l,
-    });
-    let mut groups = Vec::new();
-    loop {
-        let rucksack1 = match unwrapped_lines.next() {
-            None => return groups,
-            Some(r) => r,
-        };
-        let rucksack2 = match unwrapped_lines.next() {
-            None => panic!("Last group only has 1 elve"),
-            Some(r) => r,
-        };
-        let rucksack3 = match unwrapped_lines.next() {
-            None => panic!("Last group only has 2 elves"),
-            Some(r) => r,
-        };
-        groups.push(Group {
-            rucksacks: [rucksack1, rucksack2, rucksack3],
-        });
-    }
+    let rucksacks: Vec<ItemSet> = lines
+        .enumerate()
+        .map(|(i, line)| match line {
+            Err(why) => panic!("couldn't read line: {why}"),
+            Ok(l) => match ItemSet::from_items(&l, alphabet) {
+                Err(why) => panic!("Line {}: {why}", i + 1),
+                Ok(rucksack) => rucksack,
+            },
+        })
+        .collect();
+    rucksacks
+        .chunks(group_size)
+        .map(|rucksacks| Group {
+            rucksacks: rucksacks.to_vec(),
+        })
+        .collect()
 }
 
 fn read_lines(filename: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
@@ -1,53 +1,70 @@
+use std::env;
 use std::fs::File;
 use std::io::{self, BufRead};
 
-struct Assignment {
-    lower_bound_inclusive: i32,
-    upper_bound_inclusive: i32,
-}
+use intervals::Interval;
+
+mod coverage;
+mod reassignment;
+
+const USAGE: &str = "Usage: [--coverage] [--reassign <first>-<last>]";
 
+// Despite the name, any number of elves can share a line
 struct Pair {
-    elves_assignments: [Assignment; 2],
+    elves_assignments: Vec<Interval>,
 }
 
 fn main() {
+    let mut show_coverage = false;
+    let mut available: Option<Interval> = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--coverage" => show_coverage = true,
+            "--reassign" => match args.next() {
+                None => panic!("{USAGE}"),
+                Some(a) => available = Some(parse_assignments(&a)),
+            },
+            _ => panic!("{USAGE}"),
+        }
+    }
     let pairs = read_input("input.txt");
     pairs.iter().for_each(|p| {
-        println!(
-            "Pair {}-{},{}-{}",
-            p.elves_assignments[0].lower_bound_inclusive,
-            p.elves_assignments[0].upper_bound_inclusive,
-            p.elves_assignments[1].lower_bound_inclusive,
-            p.elves_assignments[1].upper_bound_inclusive
-        )
+        let assignments: Vec<String> = p.elves_assignments.iter().map(|a| a.to_string()).collect();
+        println!("Pair {}", assignments.join(","))
     });
-    let count = pairs.iter().filter(|p| has_assignment_overlap(*p)).count();
+    let groups: Vec<&[Interval]> = pairs
+        .iter()
+        .map(|p| p.elves_assignments.as_slice())
+        .collect();
+    if show_coverage {
+        coverage::print_coverage(&groups);
+    }
+    if let Some(available) = available {
+        reassignment::print_reassignments(&groups, &available);
+    }
+    let count = pairs.iter().filter(|p| has_assignment_overlap(p)).count();
     println!("Pairs with overlap: {count}");
 }
 
 fn has_assignment_overlap(pair: &Pair) -> bool {
-    for i in 0..pair.elves_assignments.len() - 1 {
+    for i in 0..pair.elves_assignments.len() {
         for j in i + 1..pair.elves_assignments.len() {
-            if assignment_overlaps(&pair.elves_assignments[i], &pair.elves_assignments[j])
-                || assignment_overlaps(&pair.elves_assignments[j], &pair.elves_assignments[i])
+            if pair.elves_assignments[i].contains(&pair.elves_assignments[j])
+                || pair.elves_assignments[j].contains(&pair.elves_assignments[i])
             {
                 return true;
             }
         }
     }
-    return false;
-}
-
-fn assignment_overlaps(assignment1: &Assignment, assignment2: &Assignment) -> bool {
-    return assignment1.lower_bound_inclusive <= assignment2.lower_bound_inclusive
-        && assignment1.upper_bound_inclusive >= assignment2.upper_bound_inclusive;
+    false
 }
 
-fn parse_assignments(assignments: String) -> Assignment {
-    let mut parts = assignments.split("-");
+fn parse_assignments(assignments: &str) -> Interval {
+    let mut parts = assignments.split('-');
     let lower_bound = match parts.next() {
         None => panic!("Assignment is missing lower bound"),
-        Some(a) => a.to_string().parse::<i32>(),
+        Some(a) => a.parse::<i32>(),
     };
     let lower_bound_unwrapped = match lower_bound {
         Err(why) => panic!("Could not parse lower bound as i32 {why}"),
@@ -55,31 +72,25 @@ fn parse_assignments(assignments: String) -> Assignment {
     };
     let upper_bound = match parts.next() {
         None => panic!("Assignment is missing upper bound"),
-        Some(a) => a.to_string().parse::<i32>(),
+        Some(a) => a.parse::<i32>(),
     };
     let upper_bound_unwrapped = match upper_bound {
         Err(why) => panic!("Could not parse upper bound as i32 {why}"),
         Ok(i) => i,
     };
-    return Assignment {
-        lower_bound_inclusive: lower_bound_unwrapped,
-        upper_bound_inclusive: upper_bound_unwrapped,
-    };
+    match Interval::new(lower_bound_unwrapped, upper_bound_unwrapped) {
+        Err(why) => panic!("Invalid assignment: {why}"),
+        Ok(i) => i,
+    }
 }
 
 fn parse_pair(line: String) -> Pair {
-    let mut parts = line.split(",");
-    let elve1_assignment = match parts.next() {
-        None => panic!("Elve 1 assignments missing"),
-        Some(a) => parse_assignments(a.to_string()),
-    };
-    let elve2_assignment = match parts.next() {
-        None => panic!("Elve 2 assignments missing"),
-        Some(a) => parse_assignments(a.to_string()),
-    };
-    return Pair {
-        elves_assignments: [elve1_assignment, elve2_assignment],
-    };
+    if line.is_empty() {
+        panic!("Elve assignments missing");
+    }
+    Pair {
+        elves_assignments: line.split(',').map(parse_assignments).collect(),
+    }
 }
 
 fn read_input(filename: &str) -> Vec<Pair> {
@@ -91,7 +102,7 @@ fn read_input(filename: &str) -> Vec<Pair> {
         Err(why) => panic!("couldn't read line: {why}"),
         Ok(l) => l,
     });
-    return unwrapped_lines.map(parse_pair).collect();
+    unwrapped_lines.map(parse_pair).collect()
 }
 
 fn read_lines(filename: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
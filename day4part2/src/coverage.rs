@@ -0,0 +1,115 @@
+use intervals::{Interval, IntervalSet};
+
+// A run of sections covered by the same number of elves
+struct Run {
+    sections: Interval,
+    depth: usize,
+}
+
+pub fn print_coverage(groups: &[&[Interval]]) {
+    let assignments: Vec<Interval> = groups.iter().flat_map(|g| g.iter().copied()).collect();
+    let runs = sweep(&assignments);
+    if runs.is_empty() {
+        println!("No assignments to cover");
+        return;
+    }
+    println!("Coverage:");
+    for run in &runs {
+        println!(
+            "  Sections {}: {} {}",
+            run.sections,
+            run.depth,
+            if run.depth == 1 { "elf" } else { "elves" }
+        );
+    }
+    let uncovered: IntervalSet = runs
+        .iter()
+        .filter(|r| r.depth == 0)
+        .map(|r| r.sections)
+        .collect();
+    if uncovered.is_empty() {
+        println!("Uncovered sections: none");
+    } else {
+        println!(
+            "Uncovered sections: {} ({} sections)",
+            uncovered,
+            uncovered.len()
+        );
+    }
+    let max_depth = runs.iter().map(|r| r.depth).max().unwrap_or(0);
+    let deepest: IntervalSet = runs
+        .iter()
+        .filter(|r| r.depth == max_depth)
+        .map(|r| r.sections)
+        .collect();
+    println!("Maximum coverage is {max_depth} elves, at sections {deepest}");
+    print_largest_redundant_group(groups);
+}
+
+// Between the first and the last covered section, including the runs nobody covers
+fn sweep(assignments: &[Interval]) -> Vec<Run> {
+    let mut events: Vec<(i64, isize)> = assignments
+        .iter()
+        .flat_map(|a| [(a.start() as i64, 1), (a.end() as i64 + 1, -1)])
+        .collect();
+    events.sort();
+    let mut runs = Vec::new();
+    let mut depth: isize = 0;
+    for (i, (position, change)) in events.iter().enumerate() {
+        depth += change;
+        let next = match events.get(i + 1) {
+            None => break,
+            Some((n, _)) => *n,
+        };
+        if next > *position {
+            let sections = match Interval::new(*position as i32, (next - 1) as i32) {
+                Err(why) => panic!("Invalid coverage run: {why}"),
+                Ok(s) => s,
+            };
+            runs.push(Run {
+                sections,
+                depth: depth as usize,
+            });
+        }
+    }
+    runs
+}
+
+// An elf is redundant when the rest of its group already covers all of its sections
+fn print_largest_redundant_group(groups: &[&[Interval]]) {
+    let mut largest: Option<(usize, Vec<Interval>)> = None;
+    for (i, group) in groups.iter().enumerate() {
+        let redundant: Vec<Interval> = (0..group.len())
+            .filter(|elf| {
+                let others: IntervalSet = group
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| other != elf)
+                    .map(|(_, a)| *a)
+                    .collect();
+                others.contains(&group[*elf])
+            })
+            .map(|elf| group[elf])
+            .collect();
+        let is_larger = match &largest {
+            None => !redundant.is_empty(),
+            Some((_, r)) => redundant.len() > r.len(),
+        };
+        if is_larger {
+            largest = Some((i, redundant));
+        }
+    }
+    match largest {
+        None => println!("No group has redundant elves"),
+        Some((i, redundant)) => {
+            let assignments: Vec<String> = redundant.iter().map(|a| a.to_string()).collect();
+            println!(
+                "Largest redundant group is on line {}: {} of {} elves are covered by the rest ({})",
+                i + 1,
+                redundant.len(),
+                groups[i].len(),
+                assignments.join(",")
+            );
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use intervals::Interval;
+
+// The planner tries every order of the elves, which only scales to small groups
+const MAX_PLANNED_ELVES: usize = 12;
+
+struct Reassignment {
+    assignments: Vec<Interval>,
+    changed_boundaries: u32,
+}
+
+// A partial plan: some elves placed left to right, the last one ending at `end`
+struct State {
+    end: i64,
+    changes: u32,
+    displacement: u64,
+    previous: usize,
+    elf: usize,
+    interval: (i64, i64),
+}
+
+// Only groups that overlap or reach outside the available sections are reassigned
+pub fn print_reassignments(groups: &[&[Interval]], available: &Interval) {
+    let mut reassigned = 0;
+    let mut changed_boundaries = 0;
+    let mut infeasible = 0;
+    for (i, group) in groups.iter().enumerate() {
+        let overlaps =
+            (0..group.len()).any(|a| (a + 1..group.len()).any(|b| group[a].overlaps(&group[b])));
+        if !overlaps && group.iter().all(|a| available.contains(a)) {
+            continue;
+        }
+        match reassign(group, available) {
+            Err(why) => {
+                println!("Line {}: infeasible, {why}", i + 1);
+                infeasible += 1;
+            }
+            Ok(reassignment) => {
+                println!(
+                    "Line {}: {} -> {} ({} boundaries changed)",
+                    i + 1,
+                    join(group),
+                    join(&reassignment.assignments),
+                    reassignment.changed_boundaries
+                );
+                reassigned += 1;
+                changed_boundaries += reassignment.changed_boundaries;
+            }
+        }
+    }
+    println!(
+        "Reassigned {reassigned} groups within {available} by changing {changed_boundaries} boundaries, {infeasible} infeasible"
+    );
+}
+
+fn join(assignments: &[Interval]) -> String {
+    let assignments: Vec<String> = assignments.iter().map(|a| a.to_string()).collect();
+    assignments.join(",")
+}
+
+// Makes the assignments disjoint and keeps them in the available sections, changing as few
+// boundaries as possible and moving them as little as possible on a tie
+fn reassign(assignments: &[Interval], available: &Interval) -> Result<Reassignment, String> {
+    let count = assignments.len();
+    if count as u64 > available.len() {
+        return Err(format!(
+            "{count} elves cannot get separate sections within {available}"
+        ));
+    }
+    if count > MAX_PLANNED_ELVES {
+        return Err(format!(
+            "{count} elves are too many to plan, at most {MAX_PLANNED_ELVES} are supported"
+        ));
+    }
+    let (first, last) = (available.start() as i64, available.end() as i64);
+    let mut states: Vec<Vec<State>> = (0..1usize << count).map(|_| Vec::new()).collect();
+    states[0].push(State {
+        end: first - 1,
+        changes: 0,
+        displacement: 0,
+        previous: 0,
+        elf: 0,
+        interval: (0, 0),
+    });
+    for mask in 0..states.len() {
+        for index in 0..states[mask].len() {
+            let end = states[mask][index].end;
+            for (elf, assignment) in assignments.iter().enumerate() {
+                if mask & 1 << elf != 0 {
+                    continue;
+                }
+                for (start, end) in placements(assignment, end, last) {
+                    let state = &states[mask][index];
+                    let candidate = State {
+                        end,
+                        changes: state.changes
+                            + (start != assignment.start() as i64) as u32
+                            + (end != assignment.end() as i64) as u32,
+                        displacement: state.displacement
+                            + start.abs_diff(assignment.start() as i64)
+                            + end.abs_diff(assignment.end() as i64),
+                        previous: index,
+                        elf,
+                        interval: (start, end),
+                    };
+                    insert(&mut states[mask | 1 << elf], candidate);
+                }
+            }
+        }
+    }
+    let mut mask = states.len() - 1;
+    let mut index = match (0..states[mask].len())
+        .min_by_key(|i| (states[mask][*i].changes, states[mask][*i].displacement))
+    {
+        None => return Err("No plan found".to_string()),
+        Some(i) => i,
+    };
+    let mut order = Vec::with_capacity(count);
+    while mask != 0 {
+        let state = &states[mask][index];
+        order.push((state.elf, state.interval));
+        mask &= !(1 << state.elf);
+        index = state.previous;
+    }
+    order.reverse();
+    Ok(relax(assignments, &order, last))
+}
+
+// The start is either kept or moved right after the previous elf, the end is either kept or
+// pulled in as far as possible to leave room for the next elves
+fn placements(assignment: &Interval, previous_end: i64, last: i64) -> Vec<(i64, i64)> {
+    let (start, end) = (assignment.start() as i64, assignment.end() as i64);
+    let mut starts = vec![previous_end + 1];
+    if start > previous_end + 1 && start <= last {
+        starts.push(start);
+    }
+    let mut placements = Vec::new();
+    for s in starts.into_iter().filter(|s| *s <= last) {
+        if end >= s && end <= last {
+            placements.push((s, end));
+        }
+        if end != s {
+            placements.push((s, s));
+        }
+    }
+    placements
+}
+
+// Keeps only plans that no other plan beats on both the room it leaves and its cost
+fn insert(states: &mut Vec<State>, candidate: State) {
+    let cost = (candidate.changes, candidate.displacement);
+    if states
+        .iter()
+        .any(|s| s.end <= candidate.end && (s.changes, s.displacement) <= cost)
+    {
+        return;
+    }
+    states.retain(|s| !(candidate.end <= s.end && cost <= (s.changes, s.displacement)));
+    states.push(candidate);
+}
+
+// Ends that had to be pulled in are pushed back out as far as the next elf allows
+fn relax(assignments: &[Interval], order: &[(usize, (i64, i64))], last: i64) -> Reassignment {
+    let mut planned: Vec<(i64, i64)> = vec![(0, 0); assignments.len()];
+    for (i, (elf, (start, end))) in order.iter().enumerate() {
+        let limit = match order.get(i + 1) {
+            None => last,
+            Some((_, (next_start, _))) => next_start - 1,
+        };
+        let original_end = assignments[*elf].end() as i64;
+        let end = if *end < original_end {
+            original_end.min(limit)
+        } else {
+            *end
+        };
+        planned[*elf] = (*start, end);
+    }
+    let mut changed_boundaries = 0;
+    let assignments = planned
+        .iter()
+        .zip(assignments)
+        .map(|((start, end), original)| {
+            changed_boundaries +=
+                (*start != original.start() as i64) as u32 + (*end != original.end() as i64) as u32;
+            match Interval::new(*start as i32, *end as i32) {
+                Err(why) => panic!("Invalid reassignment: {why}"),
+                Ok(i) => i,
+            }
+        })
+        .collect();
+    Reassignment {
+        assignments,
+        changed_boundaries,
+    }
+}
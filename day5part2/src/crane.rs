@@ -0,0 +1,112 @@
+use std::ops::Range;
+
+use crate::stacks::Stack;
+
+pub trait Crane {
+    fn name(&self) -> String;
+
+    // The crates come off the source stack bottom first; returns where they landed
+    fn put(&self, crates: Vec<String>, target: &mut Stack) -> Range<usize>;
+
+    // When true, moving N crates at once is the same as N moves of a single crate
+    fn moves_one_at_a_time(&self) -> bool {
+        false
+    }
+
+    fn stacks_on_top(&self) -> bool {
+        true
+    }
+}
+
+// Moves one crate at a time, so the moved crates end up reversed
+pub struct CrateMover9000;
+
+// Moves all crates at once, keeping their order
+pub struct CrateMover9001;
+
+// Moves at most `capacity` crates at a time, each batch keeping its order
+pub struct BatchCrane {
+    pub capacity: usize,
+}
+
+// Slides the moved crates under the target stack instead of on top of it
+pub struct BottomCrane;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn moves_one_at_a_time(&self) -> bool {
+        true
+    }
+
+    fn put(&self, crates: Vec<String>, target: &mut Stack) -> Range<usize> {
+        let start = target.crates.len();
+        target.crates.extend(crates.into_iter().rev());
+        start..target.crates.len()
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn put(&self, crates: Vec<String>, target: &mut Stack) -> Range<usize> {
+        let start = target.crates.len();
+        target.crates.extend(crates);
+        start..target.crates.len()
+    }
+}
+
+impl Crane for BatchCrane {
+    fn name(&self) -> String {
+        format!("crane moving {} crates at a time", self.capacity)
+    }
+
+    fn moves_one_at_a_time(&self) -> bool {
+        self.capacity == 1
+    }
+
+    fn put(&self, crates: Vec<String>, target: &mut Stack) -> Range<usize> {
+        let start = target.crates.len();
+        crates
+            .rchunks(self.capacity)
+            .for_each(|batch| target.crates.extend_from_slice(batch));
+        start..target.crates.len()
+    }
+}
+
+impl Crane for BottomCrane {
+    fn name(&self) -> String {
+        "crane dropping onto the bottom".to_string()
+    }
+
+    fn stacks_on_top(&self) -> bool {
+        false
+    }
+
+    fn put(&self, crates: Vec<String>, target: &mut Stack) -> Range<usize> {
+        let count = crates.len();
+        target.crates.splice(0..0, crates);
+        0..count
+    }
+}
+
+// 9000, 9001, batch:<capacity> or bottom
+pub fn parse_crane(name: &str) -> Result<Box<dyn Crane>, String> {
+    match name.split_once(':') {
+        Some(("batch", capacity)) => match capacity.parse::<usize>() {
+            Ok(capacity) if capacity > 0 => Ok(Box::new(BatchCrane { capacity })),
+            _ => Err(format!("Invalid crane capacity {capacity}")),
+        },
+        Some(_) => Err(format!("Unknown crane {name}")),
+        None => match name {
+            "9000" => Ok(Box::new(CrateMover9000)),
+            "9001" => Ok(Box::new(CrateMover9001)),
+            "bottom" => Ok(Box::new(BottomCrane)),
+            _ => Err(format!("Unknown crane {name}")),
+        },
+    }
+}
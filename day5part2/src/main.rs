@@ -1,120 +1,231 @@
-use std::fs::File;
+use std::env;
+use std::fs::{self, File};
 use std::io::{self, BufRead};
 
-struct Stack {
-    crates: Vec<char>,
-}
+mod crane;
+mod planner;
+mod stacks;
 
-impl Stack {
-    fn new() -> Stack {
-        Stack { crates: Vec::new() }
-    }
+use crane::Crane;
+use stacks::{Instruction, InstructionLog, Stacks};
 
-    fn remove_crates(&mut self, count: usize) -> Vec<char> {
-        let mut result: Vec<char> = Vec::new();
-        for _i in 00..count {
-            let moving_crate = match self.crates.pop() {
-                None => panic!("Not enough crates on stack"),
-                Some(c) => c,
-            };
-            result.push(moving_crate);
+const DEFAULT_CRANE: &str = "9001";
+
+const USAGE: &str =
+    "Usage: [--crane <9000|9001|batch:<capacity>|bottom>]... [--save-after <moves> <file>] [--rewind <moves>] [--verbose] [--plan <target file>]";
+
+fn main() {
+    let mut crane_names: Vec<String> = Vec::new();
+    let mut save: Option<(usize, String)> = None;
+    let mut rewind: Option<usize> = None;
+    let mut verbose = false;
+    let mut plan_target: Option<String> = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verbose" => verbose = true,
+            "--crane" => match args.next() {
+                None => panic!("{USAGE}"),
+                Some(name) => crane_names.push(name),
+            },
+            "--save-after" => match (args.next().map(|m| m.parse::<usize>()), args.next()) {
+                (Some(Ok(moves)), Some(file)) => save = Some((moves, file)),
+                _ => panic!("{USAGE}"),
+            },
+            "--plan" => match args.next() {
+                None => panic!("{USAGE}"),
+                Some(file) => plan_target = Some(file),
+            },
+            "--rewind" => match args.next().map(|m| m.parse::<usize>()) {
+                Some(Ok(moves)) => rewind = Some(moves),
+                _ => panic!("{USAGE}"),
+            },
+            _ => panic!("{USAGE}"),
         }
-        result
     }
-
-    fn add_crates(&mut self, new_crates: Vec<char>) {
-        new_crates.iter().rev().for_each(|c| self.crates.push(*c));
+    if crane_names.is_empty() {
+        crane_names.push(DEFAULT_CRANE.to_string());
     }
-}
-
-struct Stacks {
-    stacks: Vec<Stack>,
-}
-
-impl Stacks {
-    fn new(stack_count: usize) -> Stacks {
-        let mut stacks: Vec<Stack> = Vec::new();
-        for _i in 00..stack_count {
-            stacks.push(Stack::new());
+    let cranes: Vec<Box<dyn Crane>> = crane_names
+        .iter()
+        .map(|name| match crane::parse_crane(name) {
+            Err(why) => panic!("Invalid crane: {why}"),
+            Ok(c) => c,
+        })
+        .collect();
+    if (save.is_some() || plan_target.is_some()) && cranes.len() > 1 {
+        panic!("Saving a state or planning needs a single crane");
+    }
+    let (start_state, instructions) = read_input("input.txt");
+    // Only the plan is printed, so it can be used as the instructions of an input
+    if let Some(file) = plan_target {
+        let (target, _) = read_input(&file);
+        match planner::plan(&start_state, &target, cranes[0].as_ref()) {
+            Err(why) => panic!("Couldn't plan the moves to {file}: {why}"),
+            Ok(plan) => plan.iter().for_each(|i| println!("{i}")),
         }
-        Stacks { stacks }
+        return;
     }
-
-    fn perform(&mut self, instruction: &Instruction) {
+    if let Err(why) = start_state.validate(&instructions) {
+        panic!("Invalid instructions: {why}");
+    }
+    if verbose {
+        println!("Stack count = {}", start_state.stacks.len());
+        start_state.print();
+    }
+    for crane in &cranes {
+        if verbose {
+            println!("Using {}", crane.name());
+        }
+        let mut state = start_state.clone();
+        // Only kept when rewinding, as it holds a copy of every moved crate
+        let mut log = rewind.map(|_| InstructionLog::new());
+        for (step, instruction) in instructions.iter().enumerate() {
+            if let Some((moves, file)) = &save {
+                if step == *moves {
+                    save_state(file, &state, &instructions[step..]);
+                }
+            }
+            state.perform(instruction, crane.as_ref(), log.as_mut());
+            if verbose {
+                println!("{instruction}");
+                state.print();
+            }
+        }
+        if let Some((moves, file)) = &save {
+            if *moves >= instructions.len() {
+                save_state(file, &state, &[]);
+            }
+        }
+        if let (Some(moves), Some(log)) = (rewind, log.as_mut()) {
+            log.rewind(&mut state, moves);
+            print!("Rewound to move {}:\n{state}", log.len());
+        }
         println!(
-            "Move {} from {} to {}",
-            instruction.count, instruction.from, instruction.to
+            "Top of stacks with {}: {}",
+            crane.name(),
+            state.get_top_stacks()
         );
-        let moving_crates = self.stacks[instruction.from].remove_crates(instruction.count);
-        self.stacks[instruction.to].add_crates(moving_crates);
-        self.print();
     }
+}
 
-    fn print(&self) {
-        self.stacks.iter().for_each(|s| {
-            print!("[");
-            s.crates.iter().for_each(|c| print!("{c}"));
-            print!("]");
-        });
-        println!();
+// The saved state is a valid input, continuing with the instructions left
+fn save_state(filename: &str, state: &Stacks, instructions: &[Instruction]) {
+    let mut text = format!("{state}\n");
+    instructions
+        .iter()
+        .for_each(|i| text.push_str(&format!("{i}\n")));
+    if let Err(why) = fs::write(filename, text) {
+        panic!("couldn't write {filename}: {why}");
     }
+    println!("Saved state to {filename}");
+}
 
-    fn get_top_stacks(&self) -> String {
-        let tops: Vec<String> = self
-            .stacks
+// Each stack's column is found from where its number sits in the footer, so crates and
+// numbers can be any width
+fn parse_start_state(lines: &[String], footer: &str) -> Stacks {
+    let columns = parse_footer(footer, lines.len() + 1);
+    let mut stacks = Stacks::new(columns.len());
+    // Read bottom up, a stack is closed by its first empty slot
+    let mut closed = vec![false; columns.len()];
+    for (i, line) in lines.iter().enumerate().rev() {
+        let line_number = i + 1;
+        let mut filled = vec![false; columns.len()];
+        for ((start, end), label) in parse_crates(line, line_number) {
+            let below: Vec<usize> = (0..columns.len())
+                .filter(|c| columns[*c].0 < end && start < columns[*c].1)
+                .collect();
+            let stack = match below.as_slice() {
+                [stack] => *stack,
+                [] => panic!("Line {line_number}: crate [{label}] is not above a stack number"),
+                _ => panic!("Line {line_number}: crate [{label}] is above several stack numbers"),
+            };
+            if filled[stack] {
+                panic!("Line {line_number}: stack {} has two crates", stack + 1);
+            }
+            if closed[stack] {
+                panic!(
+                    "Line {line_number}: crate [{label}] floats above an empty slot of stack {}",
+                    stack + 1
+                );
+            }
+            filled[stack] = true;
+            stacks.stacks[stack].crates.push(label);
+        }
+        filled
             .iter()
-            .map(|s| s.crates.last())
-            .map(|t| match t {
-                None => &' ',
-                Some(c) => c,
-            })
-            .map(|c| c.to_string())
-            .collect();
-        tops.join("")
+            .enumerate()
+            .filter(|(_, f)| !**f)
+            .for_each(|(stack, _)| closed[stack] = true);
     }
-}
-struct Instruction {
-    count: usize,
-    from: usize,
-    to: usize,
+    stacks
 }
 
-fn main() {
-    let (mut state, instructions) = read_input("input.txt");
-    state.print();
-    instructions.iter().for_each(|i| state.perform(i));
-    state.print();
-    println!("Top of stacks: {}", state.get_top_stacks());
+// The character range of every stack number, which must count up from 1
+fn parse_footer(footer: &str, line_number: usize) -> Vec<(usize, usize)> {
+    let mut columns = Vec::new();
+    let mut start: Option<usize> = None;
+    let chars: Vec<char> = footer.chars().chain([' ']).collect();
+    for (i, c) in chars.iter().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                let number: String = chars[s..i].iter().collect();
+                if number != (columns.len() + 1).to_string() {
+                    panic!(
+                        "Line {line_number}: expected stack number {} but found {number}",
+                        columns.len() + 1
+                    );
+                }
+                columns.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    columns
 }
 
-fn parse_start_state(lines: Vec<&String>) -> Stacks {
-    let stack_count = match lines.last() {
-        None => 0,
-        Some(l) => (l.len() + 1) / 4,
-    };
-    println!("Stack count = {}", stack_count);
-    let mut stacks = Stacks::new(stack_count);
-    for line in lines.iter().rev() {
-        for i in 0..stack_count {
-            let char = line.chars().nth(1 + i * 4);
-            if let Some(c) = char {
-                if c != ' ' {
-                    stacks.stacks[i].crates.push(c);
+// The character range and label of every [crate] on the line
+fn parse_crates(line: &str, line_number: usize) -> Vec<((usize, usize), String)> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut crates = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            ' ' => i += 1,
+            '[' => {
+                let end = match chars[i..].iter().position(|c| *c == ']') {
+                    None => panic!(
+                        "Line {line_number}: crate at column {} is not closed",
+                        i + 1
+                    ),
+                    Some(e) => i + e,
+                };
+                let label: String = chars[i + 1..end].iter().collect();
+                if label.is_empty() || label.contains(char::is_whitespace) {
+                    panic!(
+                        "Line {line_number}: crate at column {} has an invalid label '{label}'",
+                        i + 1
+                    );
                 }
+                crates.push(((i, end + 1), label));
+                i = end + 1;
             }
+            c => panic!("Line {line_number}: unexpected '{c}' at column {}", i + 1),
         }
     }
-    stacks
+    crates
 }
 
-fn parse_instruction(line: &String) -> Instruction {
+fn parse_instruction(line: &str) -> Instruction {
     let mut parts = line.split(" ");
     assert_word(parts.next(), "move");
     let count = parse_instruction_part(parts.next(), "count");
     assert_word(parts.next(), "from");
-    let from = parse_instruction_part(parts.next(), "from") - 1;
+    let from = parse_stack_number(parts.next(), "from");
     assert_word(parts.next(), "to");
-    let to = parse_instruction_part(parts.next(), "to") - 1;
+    let to = parse_stack_number(parts.next(), "to");
 
     Instruction { count, from, to }
 }
@@ -141,6 +252,14 @@ fn parse_instruction_part(word: Option<&str>, name: &str) -> usize {
     }
 }
 
+// Stacks are numbered from 1 in the input but indexed from 0
+fn parse_stack_number(word: Option<&str>, name: &str) -> usize {
+    match parse_instruction_part(word, name).checked_sub(1) {
+        None => panic!("Stack numbers start at 1, but {name} is 0"),
+        Some(i) => i,
+    }
+}
+
 fn read_input(filename: &str) -> (Stacks, Vec<Instruction>) {
     let lines = match read_lines(filename) {
         Err(why) => panic!("couldn't open {filename}: {why}"),
@@ -153,15 +272,18 @@ fn read_input(filename: &str) -> (Stacks, Vec<Instruction>) {
         })
         .collect();
 
-    let start_state_lines: Vec<&String> = unwrapped_lines
-        .iter()
-        .take_while(|l| !(**l).starts_with(" 1"))
-        .collect();
-    let start_state = parse_start_state(start_state_lines);
+    // The footer is the first line made of numbers only
+    let footer = match unwrapped_lines.iter().position(|l| {
+        !l.trim().is_empty() && l.split_whitespace().all(|w| w.parse::<usize>().is_ok())
+    }) {
+        None => panic!("Stack numbers are missing"),
+        Some(f) => f,
+    };
+    let start_state = parse_start_state(&unwrapped_lines[..footer], &unwrapped_lines[footer]);
     let instructions = unwrapped_lines
         .iter()
         .skip_while(|l| !l.starts_with("move"))
-        .map(parse_instruction)
+        .map(|l| parse_instruction(l))
         .collect();
     (start_state, instructions)
 }
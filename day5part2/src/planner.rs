@@ -0,0 +1,139 @@
+use crate::crane::Crane;
+use crate::stacks::{Instruction, Stacks};
+
+// Every planned move either puts a crate in its final place or uncovers one, so a plan longer
+// than this means the planner is going round in circles
+const MAX_MOVES_PER_CRATE: usize = 64;
+
+// Builds the target stacks bottom up, one crate at a time, moving whatever covers the next
+// crate needed onto a spare stack. The plan is short rather than the shortest possible.
+pub fn plan(
+    start: &Stacks,
+    target: &Stacks,
+    crane: &dyn Crane,
+) -> Result<Vec<Instruction>, String> {
+    if !crane.stacks_on_top() {
+        return Err(format!("Can't plan for a {}", crane.name()));
+    }
+    let count = start.stacks.len();
+    if target.stacks.len() != count {
+        return Err(format!(
+            "The target has {} stacks but the start has {count}",
+            target.stacks.len()
+        ));
+    }
+    let mut start_crates: Vec<&String> = start.stacks.iter().flat_map(|s| &s.crates).collect();
+    let mut target_crates: Vec<&String> = target.stacks.iter().flat_map(|s| &s.crates).collect();
+    start_crates.sort();
+    target_crates.sort();
+    if start_crates != target_crates {
+        return Err("The target holds different crates than the start".to_string());
+    }
+    let max_moves = (start_crates.len() + 1) * MAX_MOVES_PER_CRATE;
+    let mut state = start.clone();
+    let mut plan: Vec<Instruction> = Vec::new();
+    loop {
+        let heights: Vec<usize> = state.stacks.iter().map(|s| s.crates.len()).collect();
+        let targets: Vec<usize> = target.stacks.iter().map(|s| s.crates.len()).collect();
+        let prefixes: Vec<usize> = (0..count)
+            .map(|s| {
+                state.stacks[s]
+                    .crates
+                    .iter()
+                    .zip(&target.stacks[s].crates)
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            })
+            .collect();
+        if (0..count).all(|s| prefixes[s] == heights[s] && heights[s] == targets[s]) {
+            return Ok(plan);
+        }
+        if plan.len() > max_moves {
+            return Err("Gave up, the plan keeps growing".to_string());
+        }
+        let buildable: Vec<usize> = (0..count)
+            .filter(|s| prefixes[*s] == heights[*s] && heights[*s] < targets[*s])
+            .collect();
+        if buildable.is_empty() {
+            // Clear the misplaced crates off the stack that has the fewest of them
+            let stack = match (0..count)
+                .filter(|s| prefixes[*s] < heights[*s])
+                .min_by_key(|s| heights[*s] - prefixes[*s])
+            {
+                None => return Err("No stack left to work on".to_string()),
+                Some(s) => s,
+            };
+            let spare = spare_stack(&heights, &prefixes, &[stack])?;
+            let instruction = Instruction {
+                count: heights[stack] - prefixes[stack],
+                from: stack,
+                to: spare,
+            };
+            perform(&mut state, &mut plan, instruction, crane);
+            continue;
+        }
+        // The needed crate with the fewest crates on top of it, outside of any finished part
+        let mut best: Option<(usize, usize, usize)> = None;
+        for stack in buildable {
+            let needed = &target.stacks[stack].crates[heights[stack]];
+            for source in (0..count).filter(|s| *s != stack) {
+                for level in prefixes[source]..heights[source] {
+                    if state.stacks[source].crates[level] != *needed {
+                        continue;
+                    }
+                    let above = heights[source] - level - 1;
+                    if best.is_none_or(|(a, _, _)| above < a) {
+                        best = Some((above, stack, source));
+                    }
+                }
+            }
+        }
+        let (above, stack, source) = match best {
+            None => return Err("No crate left to move".to_string()),
+            Some(b) => b,
+        };
+        if above > 0 {
+            let spare = spare_stack(&heights, &prefixes, &[stack, source])?;
+            let instruction = Instruction {
+                count: above,
+                from: source,
+                to: spare,
+            };
+            perform(&mut state, &mut plan, instruction, crane);
+        }
+        let instruction = Instruction {
+            count: 1,
+            from: source,
+            to: stack,
+        };
+        perform(&mut state, &mut plan, instruction, crane);
+    }
+}
+
+// Stacks that are already out of order are filled first, so finished stacks stay finished
+fn spare_stack(heights: &[usize], prefixes: &[usize], excluded: &[usize]) -> Result<usize, String> {
+    match (0..heights.len())
+        .filter(|s| !excluded.contains(s))
+        .min_by_key(|s| (prefixes[*s] == heights[*s], heights[*s]))
+    {
+        None => Err("Needs a spare stack to move crates out of the way".to_string()),
+        Some(s) => Ok(s),
+    }
+}
+
+fn perform(
+    state: &mut Stacks,
+    plan: &mut Vec<Instruction>,
+    instruction: Instruction,
+    crane: &dyn Crane,
+) {
+    state.perform(&instruction, crane, None);
+    if let Some(last) = plan.last_mut() {
+        if crane.moves_one_at_a_time() && last.from == instruction.from && last.to == instruction.to
+        {
+            last.count += instruction.count;
+            return;
+        }
+    }
+    plan.push(instruction);
+}
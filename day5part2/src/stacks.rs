@@ -0,0 +1,201 @@
+use std::cmp;
+use std::fmt;
+use std::ops::Range;
+
+use crate::crane::Crane;
+
+#[derive(Clone)]
+pub struct Stack {
+    pub crates: Vec<String>,
+}
+
+impl Stack {
+    fn new() -> Stack {
+        Stack { crates: Vec::new() }
+    }
+
+    // The removed crates keep their order, bottom first
+    fn remove_crates(&mut self, count: usize) -> Vec<String> {
+        match self.crates.len().checked_sub(count) {
+            None => panic!("Not enough crates on stack"),
+            Some(at) => self.crates.split_off(at),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Stacks {
+    pub stacks: Vec<Stack>,
+}
+
+pub struct Instruction {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+// Enough of a performed move to take it back, whatever the crane did with the crates
+struct MoveRecord {
+    from: usize,
+    to: usize,
+    crates: Vec<String>,
+    landed: Range<usize>,
+}
+
+#[derive(Default)]
+pub struct InstructionLog {
+    records: Vec<MoveRecord>,
+}
+
+impl Stacks {
+    pub fn new(stack_count: usize) -> Stacks {
+        let mut stacks: Vec<Stack> = Vec::new();
+        for _i in 0..stack_count {
+            stacks.push(Stack::new());
+        }
+        Stacks { stacks }
+    }
+
+    pub fn perform(
+        &mut self,
+        instruction: &Instruction,
+        crane: &dyn Crane,
+        log: Option<&mut InstructionLog>,
+    ) {
+        let moving_crates = self.stacks[instruction.from].remove_crates(instruction.count);
+        let crates = log.as_ref().map(|_| moving_crates.clone());
+        let landed = crane.put(moving_crates, &mut self.stacks[instruction.to]);
+        if let (Some(log), Some(crates)) = (log, crates) {
+            log.records.push(MoveRecord {
+                from: instruction.from,
+                to: instruction.to,
+                crates,
+                landed,
+            });
+        }
+    }
+
+    // Replays the stack heights only, so every problem is found before a crate is moved
+    pub fn validate(&self, instructions: &[Instruction]) -> Result<(), String> {
+        let mut heights: Vec<usize> = self.stacks.iter().map(|s| s.crates.len()).collect();
+        for (i, instruction) in instructions.iter().enumerate() {
+            let problem = if instruction.from >= heights.len() {
+                Some(format!("there is no stack {}", instruction.from + 1))
+            } else if instruction.to >= heights.len() {
+                Some(format!("there is no stack {}", instruction.to + 1))
+            } else if heights[instruction.from] < instruction.count {
+                Some(format!(
+                    "stack {} only has {} crates",
+                    instruction.from + 1,
+                    heights[instruction.from]
+                ))
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                let heights: Vec<String> = heights.iter().map(|h| h.to_string()).collect();
+                return Err(format!(
+                    "Instruction {} '{instruction}': {problem}, stack heights are {}",
+                    i + 1,
+                    heights.join(" ")
+                ));
+            }
+            heights[instruction.from] -= instruction.count;
+            heights[instruction.to] += instruction.count;
+        }
+        Ok(())
+    }
+
+    pub fn print(&self) {
+        self.stacks.iter().for_each(|s| {
+            print!("[");
+            print!("{}", s.crates.concat());
+            print!("]");
+        });
+        println!();
+    }
+
+    pub fn get_top_stacks(&self) -> String {
+        let tops: Vec<&str> = self
+            .stacks
+            .iter()
+            .map(|s| match s.crates.last() {
+                None => " ",
+                Some(c) => c,
+            })
+            .collect();
+        tops.join("")
+    }
+}
+
+impl InstructionLog {
+    pub fn new() -> Self {
+        InstructionLog::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    // Undoes the latest moves until only the first `moves` remain performed
+    pub fn rewind(&mut self, stacks: &mut Stacks, moves: usize) {
+        while self.records.len() > moves {
+            let record = match self.records.pop() {
+                None => break,
+                Some(r) => r,
+            };
+            stacks.stacks[record.to].crates.drain(record.landed);
+            stacks.stacks[record.from].crates.extend(record.crates);
+        }
+    }
+}
+
+// The puzzle's drawing: one column of [X] per stack, topped up with spaces, above the numbered
+// footer, so that it reads back into the same stacks. Columns widen to fit the longest label
+// or stack number.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label_width = self
+            .stacks
+            .iter()
+            .flat_map(|s| s.crates.iter())
+            .map(|c| c.chars().count())
+            .max()
+            .unwrap_or(1);
+        let width = cmp::max(label_width + 2, self.stacks.len().to_string().len());
+        let height = self
+            .stacks
+            .iter()
+            .map(|s| s.crates.len())
+            .max()
+            .unwrap_or(0);
+        for level in (0..height).rev() {
+            let row: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|s| match s.crates.get(level) {
+                    None => " ".repeat(width),
+                    Some(c) => format!("{:^width$}", format!("[{c}]")),
+                })
+                .collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+        let footer: Vec<String> = (1..=self.stacks.len())
+            .map(|i| format!("{i:^width$}"))
+            .collect();
+        writeln!(f, "{}", footer.join(" "))
+    }
+}
+
+// Stacks are numbered from 1 in the puzzle
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
@@ -1,58 +1,103 @@
+use std::env;
 use std::fs::File;
-use std::io::{self, BufRead};
+use std::io::{BufRead, BufReader};
 
-const PACKET_SIZE: usize = 14;
+mod marker;
+mod protocol;
 
-fn main() {
-    let lines = read_input("input.txt");
-    lines
-        .iter()
-        .map(solve)
-        .for_each(|s| println!("Marker ends at {s}"));
-}
+use marker::Markers;
+use protocol::Protocol;
+
+const DEFAULT_WINDOW_SIZE: usize = 14;
+
+const USAGE: &str = "Usage: [window size] [--all] [--decode] [--protocol <file>]";
 
-fn solve(line: &String) -> i32 {
-    let mut chars = line.chars();
-    let mut buffer = ['-'; PACKET_SIZE - 1];
-    let mut counter: i32 = 0;
-    while let Some(c) = chars.next() {
-        if counter >= PACKET_SIZE as i32 - 1 {
-            if all_different(&buffer) && buffer.iter().all(|b| *b != c) {
-                return counter + 1;
-            }
+fn main() {
+    let mut window_size = DEFAULT_WINDOW_SIZE;
+    let mut all = false;
+    let mut decode = false;
+    let mut protocol_file: Option<String> = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--decode" => decode = true,
+            "--protocol" => match args.next() {
+                None => panic!("{USAGE}"),
+                Some(file) => {
+                    protocol_file = Some(file);
+                    decode = true;
+                }
+            },
+            s => match s.parse::<usize>() {
+                Ok(size) if size > 0 => window_size = size,
+                _ => panic!("{USAGE}"),
+            },
         }
-        let index = (counter % (PACKET_SIZE as i32 - 1)) as usize;
-        buffer[index] = c;
-        counter += 1;
     }
-    -1
-}
-
-fn all_different(buffer: &[char; PACKET_SIZE - 1]) -> bool {
-    for i in 0..(PACKET_SIZE - 2) {
-        for j in (i + 1)..(PACKET_SIZE - 1) {
-            if buffer[i] == buffer[j] {
-                return false;
-            }
+    let file = match File::open("input.txt") {
+        Err(why) => panic!("couldn't open input.txt: {why}"),
+        Ok(f) => f,
+    };
+    if decode {
+        let protocol = match protocol_file {
+            None => Protocol::parse(protocol::DEFAULT_PROTOCOL),
+            Some(f) => Protocol::load(&f),
+        };
+        match protocol {
+            Err(why) => panic!("Invalid protocol: {why}"),
+            Ok(p) => print_frames(&p, file),
         }
+        return;
     }
-    true
+    let mut markers = Markers::new(file, window_size);
+    // The first marker of every line
+    let mut first: Vec<Option<u64>> = Vec::new();
+    for marker in markers.by_ref() {
+        let marker = match marker {
+            Err(why) => panic!("couldn't read input.txt: {why}"),
+            Ok(m) => m,
+        };
+        if all {
+            println!(
+                "Line {} has a marker ending at {} (byte {})",
+                marker.line + 1,
+                marker.offset,
+                marker.byte_offset
+            );
+        }
+        if first.len() <= marker.line {
+            first.resize(marker.line + 1, None);
+        }
+        first[marker.line].get_or_insert(marker.offset);
+    }
+    first.resize(markers.lines(), None);
+    first.iter().for_each(|f| match f {
+        None => println!("No marker found"),
+        Some(offset) => println!("Marker ends at {offset}"),
+    });
 }
 
-fn read_input(filename: &str) -> Vec<String> {
-    let lines = match read_lines(filename) {
-        Err(why) => panic!("couldn't open {filename}: {why}"),
-        Ok(lines) => lines,
-    };
-    return lines
-        .map(|line| match line {
+fn print_frames(protocol: &Protocol, file: File) {
+    let mut count = 0;
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = match line {
             Err(why) => panic!("couldn't read line: {why}"),
             Ok(l) => l,
-        })
-        .collect();
-}
-
-fn read_lines(filename: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+        };
+        for frame in protocol.decode(&line) {
+            println!(
+                "Line {} {} at {} (byte {}), {} symbols{}: {}",
+                i + 1,
+                frame.kind,
+                frame.offset,
+                frame.byte_offset,
+                frame.length,
+                if frame.truncated { ", truncated" } else { "" },
+                frame.payload
+            );
+            count += 1;
+        }
+    }
+    println!("Decoded {count} frames");
 }
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read};
+use std::str;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+// Counts every symbol in the window, so each step costs the same whatever the window size
+pub struct MarkerDetector {
+    size: usize,
+    window: VecDeque<char>,
+    counts: HashMap<char, usize>,
+    // Symbols seen more than once in the window
+    repeated: usize,
+}
+
+impl MarkerDetector {
+    pub fn new(size: usize) -> MarkerDetector {
+        if size == 0 {
+            panic!("Window size must be at least 1");
+        }
+        MarkerDetector {
+            size,
+            window: VecDeque::with_capacity(size),
+            counts: HashMap::new(),
+            repeated: 0,
+        }
+    }
+
+    // True when the symbol ends a full window of distinct symbols
+    pub fn push(&mut self, symbol: char) -> bool {
+        if self.window.len() == self.size {
+            if let Some(oldest) = self.window.pop_front() {
+                self.remove(oldest);
+            }
+        }
+        self.window.push_back(symbol);
+        let count = self.counts.entry(symbol).or_insert(0);
+        *count += 1;
+        if *count == 2 {
+            self.repeated += 1;
+        }
+        self.window.len() == self.size && self.repeated == 0
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.counts.clear();
+        self.repeated = 0;
+    }
+
+    fn remove(&mut self, symbol: char) {
+        let count = match self.counts.get_mut(&symbol) {
+            None => return,
+            Some(c) => c,
+        };
+        if *count == 2 {
+            self.repeated -= 1;
+        }
+        *count -= 1;
+        if *count == 0 {
+            self.counts.remove(&symbol);
+        }
+    }
+}
+
+// Where a window of distinct symbols ends, counting the window's last symbol
+pub struct Marker {
+    // From 0
+    pub line: usize,
+    // In characters from the start of the line
+    pub offset: u64,
+    // In bytes from the start of the stream
+    pub byte_offset: u64,
+}
+
+// Reads the stream in chunks and yields every marker; each line is a separate datastream
+pub struct Markers<R: Read> {
+    reader: R,
+    detector: MarkerDetector,
+    chunk: Vec<u8>,
+    // Bytes of a character split across chunks
+    pending: Vec<u8>,
+    text: String,
+    position: usize,
+    line: usize,
+    offset: u64,
+    byte_offset: u64,
+    done: bool,
+}
+
+impl<R: Read> Markers<R> {
+    pub fn new(reader: R, window_size: usize) -> Markers<R> {
+        Markers {
+            reader,
+            detector: MarkerDetector::new(window_size),
+            chunk: vec![0; CHUNK_SIZE],
+            pending: Vec::new(),
+            text: String::new(),
+            position: 0,
+            line: 0,
+            offset: 0,
+            byte_offset: 0,
+            done: false,
+        }
+    }
+
+    // The lines read so far, including an unfinished last line
+    pub fn lines(&self) -> usize {
+        self.line + (self.offset > 0) as usize
+    }
+
+    // Decodes the next chunk into `text`, false at the end of the stream
+    fn refill(&mut self) -> io::Result<bool> {
+        let read = loop {
+            match self.reader.read(&mut self.chunk) {
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => continue,
+                Err(why) => return Err(why),
+                Ok(read) => break read,
+            }
+        };
+        if read == 0 {
+            if !self.pending.is_empty() {
+                return Err(invalid_data(self.byte_offset));
+            }
+            return Ok(false);
+        }
+        self.pending.extend_from_slice(&self.chunk[..read]);
+        let valid = match str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            Err(why) if why.error_len().is_none() => why.valid_up_to(),
+            Err(why) => return Err(invalid_data(self.byte_offset + why.valid_up_to() as u64)),
+        };
+        let rest = self.pending.split_off(valid);
+        self.text = match String::from_utf8(std::mem::replace(&mut self.pending, rest)) {
+            Err(why) => return Err(io::Error::new(io::ErrorKind::InvalidData, why)),
+            Ok(text) => text,
+        };
+        self.position = 0;
+        Ok(true)
+    }
+}
+
+impl<R: Read> Iterator for Markers<R> {
+    type Item = io::Result<Marker>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let symbol = match self.text[self.position..].chars().next() {
+                Some(s) => s,
+                None => {
+                    match self.refill() {
+                        Err(why) => {
+                            self.done = true;
+                            return Some(Err(why));
+                        }
+                        Ok(false) => self.done = true,
+                        Ok(true) => {}
+                    }
+                    continue;
+                }
+            };
+            self.position += symbol.len_utf8();
+            self.byte_offset += symbol.len_utf8() as u64;
+            match symbol {
+                '\n' => {
+                    self.line += 1;
+                    self.offset = 0;
+                    self.detector.reset();
+                }
+                '\r' => {}
+                _ => {
+                    self.offset += 1;
+                    if self.detector.push(symbol) {
+                        return Some(Ok(Marker {
+                            line: self.line,
+                            offset: self.offset,
+                            byte_offset: self.byte_offset,
+                        }));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+fn invalid_data(byte_offset: u64) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid UTF-8 at byte {byte_offset}"),
+    )
+}
@@ -0,0 +1,245 @@
+use std::fs;
+
+use crate::marker::MarkerDetector;
+
+// Protocol files declare one frame kind per line, '#' starts a comment:
+//   frame <name> <marker size> until-marker
+//   frame <name> <marker size> length <symbols>
+//   frame <name> <marker size> line
+// A frame's payload starts right after its marker and runs until the next marker of the same
+// kind starts, for a fixed number of symbols, or to the end of the line. An until-marker payload
+// holds at least one symbol, the search for the next marker starts after it.
+// The puzzle streams hold one start-of-packet and one start-of-message marker each.
+pub const DEFAULT_PROTOCOL: &str = "
+frame packet 4 line
+frame message 14 line
+";
+
+enum Framing {
+    UntilMarker,
+    Length(usize),
+    Line,
+}
+
+struct FrameRule {
+    name: String,
+    marker_size: usize,
+    framing: Framing,
+}
+
+pub struct Protocol {
+    rules: Vec<FrameRule>,
+}
+
+pub struct Frame<'a> {
+    pub kind: &'a str,
+    // In characters and in bytes from the start of the line
+    pub offset: usize,
+    pub byte_offset: usize,
+    // In characters
+    pub length: usize,
+    pub payload: String,
+    // A fixed length frame cut short by the end of the line
+    pub truncated: bool,
+}
+
+impl Protocol {
+    pub fn load(filename: &str) -> Result<Protocol, String> {
+        match fs::read_to_string(filename) {
+            Err(why) => Err(format!("couldn't read {filename}: {why}")),
+            Ok(text) => Protocol::parse(&text).map_err(|why| format!("{filename}: {why}")),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Protocol, String> {
+        let mut rules: Vec<FrameRule> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("");
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.is_empty() {
+                continue;
+            }
+            let rule = parse_rule(&words).map_err(|why| format!("line {}: {why}", i + 1))?;
+            if rules.iter().any(|r| r.name == rule.name) {
+                return Err(format!(
+                    "line {}: frame {} is declared twice",
+                    i + 1,
+                    rule.name
+                ));
+            }
+            rules.push(rule);
+        }
+        if rules.is_empty() {
+            return Err("No frames declared".to_string());
+        }
+        Ok(Protocol { rules })
+    }
+
+    // Frames of every kind, ordered by where they start
+    pub fn decode(&self, line: &str) -> Vec<Frame<'_>> {
+        let symbols: Vec<(usize, char)> = line.char_indices().collect();
+        let mut frames: Vec<Frame> = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.decode(line, &symbols))
+            .collect();
+        frames.sort_by_key(|f| f.offset);
+        frames
+    }
+}
+
+impl FrameRule {
+    fn decode<'a>(&'a self, line: &str, symbols: &[(usize, char)]) -> Vec<Frame<'a>> {
+        let mut frames = Vec::new();
+        let mut detector = MarkerDetector::new(self.marker_size);
+        let mut start: Option<usize> = None;
+        let mut i = 0;
+        while i < symbols.len() {
+            let (_, symbol) = symbols[i];
+            i += 1;
+            if !detector.push(symbol) {
+                continue;
+            }
+            detector.reset();
+            match self.framing {
+                Framing::UntilMarker => {
+                    if let Some(s) = start {
+                        frames.push(self.frame(line, symbols, s, i - self.marker_size, false));
+                    }
+                    start = Some(i);
+                    i += 1;
+                }
+                Framing::Length(length) => {
+                    let end = i.saturating_add(length);
+                    frames.push(self.frame(
+                        line,
+                        symbols,
+                        i,
+                        end.min(symbols.len()),
+                        end > symbols.len(),
+                    ));
+                    i = end;
+                }
+                Framing::Line => {
+                    frames.push(self.frame(line, symbols, i, symbols.len(), false));
+                    break;
+                }
+            }
+        }
+        if let Some(s) = start.filter(|s| *s < symbols.len()) {
+            frames.push(self.frame(line, symbols, s, symbols.len(), false));
+        }
+        frames
+    }
+
+    fn frame<'a>(
+        &'a self,
+        line: &str,
+        symbols: &[(usize, char)],
+        start: usize,
+        end: usize,
+        truncated: bool,
+    ) -> Frame<'a> {
+        let byte = |i: usize| symbols.get(i).map_or(line.len(), |(b, _)| *b);
+        Frame {
+            kind: &self.name,
+            offset: start,
+            byte_offset: byte(start),
+            length: end - start,
+            payload: line[byte(start)..byte(end)].to_string(),
+            truncated,
+        }
+    }
+}
+
+fn parse_rule(words: &[&str]) -> Result<FrameRule, String> {
+    let (name, marker_size, framing) = match words {
+        ["frame", name, size, "until-marker"] => (name, size, Framing::UntilMarker),
+        ["frame", name, size, "line"] => (name, size, Framing::Line),
+        ["frame", name, size, "length", length] => match length.parse::<usize>() {
+            Err(why) => return Err(format!("Invalid length {length}: {why}")),
+            Ok(l) => (name, size, Framing::Length(l)),
+        },
+        _ => return Err(format!("Invalid declaration '{}'", words.join(" "))),
+    };
+    match marker_size.parse::<usize>() {
+        Ok(size) if size > 0 => Ok(FrameRule {
+            name: name.to_string(),
+            marker_size: size,
+            framing,
+        }),
+        _ => Err(format!("Invalid marker size {marker_size}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(protocol: &str, line: &str) -> Vec<(String, usize, String)> {
+        let protocol = match Protocol::parse(protocol) {
+            Err(why) => panic!("Invalid protocol: {why}"),
+            Ok(p) => p,
+        };
+        protocol
+            .decode(line)
+            .iter()
+            .map(|f| (f.kind.to_string(), f.offset, f.payload.clone()))
+            .collect()
+    }
+
+    fn frame(kind: &str, offset: usize, payload: &str) -> (String, usize, String) {
+        (kind.to_string(), offset, payload.to_string())
+    }
+
+    #[test]
+    fn default_protocol_finds_the_example_markers() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (line, packet, message) in examples {
+            assert_eq!(
+                decode(DEFAULT_PROTOCOL, line),
+                vec![
+                    frame("packet", packet, &line[packet..]),
+                    frame("message", message, &line[message..]),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn until_marker_frames_are_never_empty() {
+        let frames = decode(
+            "frame packet 4 until-marker",
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+        );
+        assert_eq!(
+            frames,
+            vec![
+                frame("packet", 7, "g"),
+                frame("packet", 12, "p"),
+                frame("packet", 17, "n"),
+                frame("packet", 22, "w"),
+                frame("packet", 27, "mlb"),
+            ]
+        );
+    }
+
+    #[test]
+    fn length_frames_are_cut_at_the_end_of_the_line() {
+        let protocol = match Protocol::parse("frame packet 4 length 18446744073709551615") {
+            Err(why) => panic!("Invalid protocol: {why}"),
+            Ok(p) => p,
+        };
+        let frames = protocol.decode("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].offset, 7);
+        assert_eq!(frames[0].payload, "gbljsphdztnvjfqwrcgsmlb");
+        assert!(frames[0].truncated);
+    }
+}
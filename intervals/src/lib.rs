@@ -0,0 +1,302 @@
+//! Inclusive integer intervals and sets of disjoint intervals
+
+use std::cmp;
+use std::fmt;
+
+// Both bounds are inclusive, so an interval always holds at least one section
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    start: i32,
+    end: i32,
+}
+
+// Sorted, disjoint intervals; touching intervals are merged
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl Interval {
+    pub fn new(start: i32, end: i32) -> Result<Interval, String> {
+        if start > end {
+            return Err(format!("Interval {start}-{end} ends before it starts"));
+        }
+        Ok(Interval { start, end })
+    }
+
+    pub fn start(&self) -> i32 {
+        self.start
+    }
+
+    pub fn end(&self) -> i32 {
+        self.end
+    }
+
+    pub fn len(&self) -> u64 {
+        (self.end as i64 - self.start as i64 + 1) as u64
+    }
+
+    pub fn contains_point(&self, point: i32) -> bool {
+        self.start <= point && point <= self.end
+    }
+
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(
+            cmp::max(self.start, other.start),
+            cmp::min(self.end, other.end),
+        )
+        .ok()
+    }
+
+    pub fn union(&self, other: &Interval) -> IntervalSet {
+        let mut set = IntervalSet::from(*self);
+        set.insert(*other);
+        set
+    }
+
+    pub fn difference(&self, other: &Interval) -> IntervalSet {
+        IntervalSet::from(*self).difference(&IntervalSet::from(*other))
+    }
+
+    fn touches(&self, other: &Interval) -> bool {
+        self.start as i64 <= other.end as i64 + 1 && other.start as i64 <= self.end as i64 + 1
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        IntervalSet {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut set = IntervalSet::new();
+        intervals.into_iter().for_each(|i| set.insert(i));
+        set
+    }
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains_point(&self, point: i32) -> bool {
+        self.intervals.iter().any(|i| i.contains_point(point))
+    }
+
+    pub fn contains(&self, interval: &Interval) -> bool {
+        self.intervals.iter().any(|i| i.contains(interval))
+    }
+
+    pub fn overlaps(&self, interval: &Interval) -> bool {
+        self.intervals.iter().any(|i| i.overlaps(interval))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let mut merged = interval;
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+        let mut inserted = false;
+        for i in &self.intervals {
+            if i.touches(&merged) {
+                merged = Interval {
+                    start: cmp::min(i.start, merged.start),
+                    end: cmp::max(i.end, merged.end),
+                };
+            } else if i.end < merged.start {
+                intervals.push(*i);
+            } else {
+                if !inserted {
+                    intervals.push(merged);
+                    inserted = true;
+                }
+                intervals.push(*i);
+            }
+        }
+        if !inserted {
+            intervals.push(merged);
+        }
+        self.intervals = intervals;
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(common) = a.intersection(b) {
+                intervals.push(common);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        for interval in &self.intervals {
+            let mut rest = Some(*interval);
+            for removed in other.intervals.iter().filter(|r| r.overlaps(interval)) {
+                let current = match rest {
+                    None => break,
+                    Some(c) => c,
+                };
+                if current.start < removed.start {
+                    intervals.push(Interval {
+                        start: current.start,
+                        end: removed.start - 1,
+                    });
+                }
+                rest = if removed.end < current.end {
+                    Some(Interval {
+                        start: removed.end + 1,
+                        end: current.end,
+                    })
+                } else {
+                    None
+                };
+            }
+            if let Some(r) = rest {
+                intervals.push(r);
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let intervals: Vec<String> = self.intervals.iter().map(|i| i.to_string()).collect();
+        write!(f, "{}", intervals.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: i32, end: i32) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| interval(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn new_rejects_reversed_bounds() {
+        assert!(Interval::new(3, 2).is_err());
+        assert_eq!(interval(2, 2).len(), 1);
+        assert_eq!(interval(i32::MIN, i32::MAX).len(), 1 << 32);
+    }
+
+    #[test]
+    fn insert_merges_touching_and_overlapping() {
+        let mut intervals = IntervalSet::new();
+        intervals.insert(interval(5, 6));
+        intervals.insert(interval(1, 2));
+        assert_eq!(intervals.to_string(), "1-2,5-6");
+        intervals.insert(interval(3, 4));
+        assert_eq!(intervals.to_string(), "1-6");
+        intervals.insert(interval(4, 9));
+        assert_eq!(intervals.to_string(), "1-9");
+        intervals.insert(interval(11, 12));
+        assert_eq!(intervals.to_string(), "1-9,11-12");
+        assert_eq!(intervals.len(), 11);
+    }
+
+    #[test]
+    fn interval_difference() {
+        assert_eq!(
+            interval(1, 10).difference(&interval(3, 4)).to_string(),
+            "1-2,5-10"
+        );
+        assert_eq!(
+            interval(1, 10).difference(&interval(0, 5)).to_string(),
+            "6-10"
+        );
+        assert!(interval(1, 10).difference(&interval(1, 10)).is_empty());
+        assert_eq!(
+            interval(1, 3).difference(&interval(5, 6)).to_string(),
+            "1-3"
+        );
+    }
+
+    #[test]
+    fn interval_intersection() {
+        assert_eq!(
+            interval(1, 5).intersection(&interval(3, 8)),
+            Some(interval(3, 5))
+        );
+        assert_eq!(
+            interval(1, 5).intersection(&interval(5, 8)),
+            Some(interval(5, 5))
+        );
+        assert_eq!(interval(1, 5).intersection(&interval(6, 8)), None);
+    }
+
+    #[test]
+    fn set_difference() {
+        let s = set(&[(1, 3), (10, 12), (4, 5), (20, 20), (7, 8)]);
+        let t = set(&[(2, 11), (19, 25)]);
+        assert_eq!(s.to_string(), "1-5,7-8,10-12,20-20");
+        assert_eq!(s.difference(&t).to_string(), "1-1,12-12");
+        assert_eq!(t.difference(&s).to_string(), "6-6,9-9,19-19,21-25");
+        assert!(s.difference(&s).is_empty());
+    }
+
+    #[test]
+    fn set_intersection() {
+        let s = set(&[(1, 3), (10, 12), (4, 5), (20, 20), (7, 8)]);
+        let t = set(&[(2, 11), (19, 25)]);
+        assert_eq!(s.intersection(&t).to_string(), "2-5,7-8,10-11,20-20");
+        assert_eq!(s.intersection(&t), t.intersection(&s));
+        assert!(s.intersection(&IntervalSet::new()).is_empty());
+        assert_eq!(s.union(&t).to_string(), "1-12,19-25");
+    }
+}